@@ -1,5 +1,6 @@
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Map, Symbol,
+    TryFromVal, Val, Vec,
 };
 use stellar_access::access_control::{self as access_control, AccessControl};
 use stellar_macros::{default_impl, only_role};
@@ -35,6 +36,7 @@ pub struct CollateralInput {
 pub struct Loan {
     pub borrower: Address,
     pub collaterals: Vec<CollateralInput>,
+    pub borrow_asset: Address,
     pub principal: i128,
     pub outstanding_debt: i128,
     pub interest_rate: i128,        // Basis points (e.g., 700 = 7%, 1400 = 14%)
@@ -47,6 +49,96 @@ pub struct Loan {
     pub yield_share_percent: i128, // Basis points (e.g., 1000 = 10%, 2000 = 20%)
 }
 
+/// Loan record stored before the pool supported several borrow assets;
+/// every such loan was a USDC loan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyLoan {
+    pub borrower: Address,
+    pub collaterals: Vec<CollateralInput>,
+    pub principal: i128,
+    pub outstanding_debt: i128,
+    pub interest_rate: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub last_interest_update: u64,
+    pub warnings_issued: u32,
+    pub last_warning_time: u64,
+    pub penalties: i128,
+    pub yield_share_percent: i128,
+}
+
+/// Configuration of an asset that can be borrowed from the pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowAssetConfig {
+    pub interest_rate: i128,       // Basis points
+    pub yield_share_percent: i128, // Basis points
+}
+
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Proof-of-reserve record published by the oracle for an RWA token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub nav: i128,
+    pub reserve_amount: i128,
+    pub custodian_id: Symbol,
+    pub report_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// Price sources for an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    pub primary: Address,
+    pub primary_decimals: u32,
+    pub secondary: Option<Address>,
+    pub secondary_decimals: u32,
+    pub max_deviation_bps: i128, // Max allowed disagreement between primary and secondary
+    pub max_age: Option<u64>,    // Pool override of the oracles' price max age
+}
+
+/// Per-asset staleness limit published by oracles that support it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedConfig {
+    pub max_age: u64,
+}
+
+/// What the pool allows when no reliable price is available for a loan's assets
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OracleFailureMode {
+    RepayOnly = 0,  // Borrowing, withdrawals and keepers blocked; repayments allowed
+    Frozen = 1,     // All loan actions blocked, including repayments
+}
+
+/// Decimals cached at registration, used to normalize oracle valuations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDecimals {
+    pub token_decimals: u32,
+    pub price_decimals: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LPDeposit {
@@ -57,6 +149,27 @@ pub struct LPDeposit {
     pub total_interest_earned: i128,
 }
 
+/// Result of a keeper action on a single borrower
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum KeeperOutcome {
+    NoAction = 0,    // Loan exists but did not qualify
+    Warned = 1,      // Warning and penalty issued
+    Flagged = 2,     // Liquidation flag emitted
+    Liquidated = 3,  // Loan liquidated and closed
+    NoLoan = 4,      // Borrower has no active loan
+    StalePrice = 5,  // Oracle price too old to act on
+    WarnedAndFlagged = 6, // Warning issued and liquidation flag emitted
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchOutcome {
+    pub borrower: Address,
+    pub outcome: KeeperOutcome,
+}
+
 // ============================================================================
 // Storage Keys
 // ============================================================================
@@ -69,24 +182,52 @@ pub enum DataKey {
     UsdcAddress,
     LiquidationBot,
     Loan(Address),              // borrower -> Loan
-    LPDeposit(Address),         // depositor -> LPDeposit
+    LPDeposit(Address, Address), // (depositor, asset) -> LPDeposit
     Vaults(Address),            // stRWA token -> vault address
+    CollateralRatio(Address),   // stRWA token -> min collateral ratio (bps)
+    BorrowAssets,               // Vec<Address> of borrowable assets
+    BorrowAsset(Address),       // asset -> BorrowAssetConfig
+    AssetDecimals(Address),     // token -> AssetDecimals
+    OracleConfig(Address),      // asset -> OracleConfig
+    OracleFailureMode,          // OracleFailureMode when all prices fail
+    AttestationMaxAge,          // Seconds a reserve attestation stays valid
+    TotalLiquidity(Address),    // asset -> total liquidity in pool
+    TotalLockedLiquidity(Address), // asset -> liquidity locked in loans
+}
+
+/// USDC-only liquidity keys used before the pool supported several borrow assets
+#[contracttype]
+#[derive(Clone)]
+pub enum LegacyDataKey {
+    LPDeposit(Address),         // depositor -> LPDeposit
     TotalLiquidity,             // Total USDC in pool
     TotalLockedLiquidity,       // Total USDC locked in loans
 }
 
+/// Default minimum collateral ratio (140%) for assets without their own setting
+const DEFAULT_COLLATERAL_RATIO_BPS: i128 = 14000;
+
+/// All USDC values are expressed with the USDC token's 7 decimals
+const VALUE_DECIMALS: u32 = 7;
+
+/// Max price age when the oracle publishes no feed config for an asset
+const DEFAULT_PRICE_MAX_AGE: u64 = 86400;
+
+/// Default validity of a reserve attestation (30 days)
+const DEFAULT_ATTESTATION_MAX_AGE: u64 = 30 * 24 * 60 * 60;
+
 // ============================================================================
 // Manual Client Interfaces for Cross-Contract Calls
 // ============================================================================
 
-pub struct UsdcClient<'a> {
+pub struct TokenClient<'a> {
     env: &'a Env,
     address: &'a Address,
 }
 
-impl<'a> UsdcClient<'a> {
+impl<'a> TokenClient<'a> {
     pub fn new(env: &'a Env, address: &'a Address) -> Self {
-        UsdcClient { env, address }
+        TokenClient { env, address }
     }
 
     pub fn transfer(&self, from: &Address, to: &Address, amount: &i128) {
@@ -104,6 +245,22 @@ impl<'a> UsdcClient<'a> {
             (id,).into_val(self.env),
         )
     }
+
+    pub fn decimals(&self) -> u32 {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "decimals"),
+            ().into_val(self.env),
+        )
+    }
+
+    pub fn total_supply(&self) -> i128 {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "total_supply"),
+            ().into_val(self.env),
+        )
+    }
 }
 
 pub struct StRwaClient<'a> {
@@ -175,6 +332,14 @@ impl<'a> VaultClient<'a> {
         )
     }
 
+    pub fn update_borrowed_amount(&self, user: &Address, new_amount: &i128) {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "update_borrowed_amount"),
+            (user, new_amount).into_val(self.env),
+        )
+    }
+
     pub fn set_lp_liquidity_used(&self, user: &Address, amount: &i128) {
         self.env.invoke_contract(
             self.address,
@@ -182,6 +347,22 @@ impl<'a> VaultClient<'a> {
             (user, amount).into_val(self.env),
         )
     }
+
+    pub fn get_rwa_token(&self) -> Address {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "get_rwa_token"),
+            ().into_val(self.env),
+        )
+    }
+
+    pub fn on_collateral_seized(&self, user: &Address, amount: &i128, recipient: &Address) -> i128 {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "on_collateral_seized"),
+            (user, amount, recipient).into_val(self.env),
+        )
+    }
 }
 
 pub struct OracleClient<'a> {
@@ -194,13 +375,43 @@ impl<'a> OracleClient<'a> {
         OracleClient { env, address }
     }
 
-    pub fn get_price(&self, asset: &Address) -> (i128, u64) {
+    /// SEP-40 `lastprice` for a Stellar asset
+    pub fn lastprice(&self, asset: &Address) -> Option<PriceData> {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "lastprice"),
+            (Asset::Stellar(asset.clone()),).into_val(self.env),
+        )
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "decimals"),
+            ().into_val(self.env),
+        )
+    }
+
+    pub fn get_attestation(&self, rwa_token: &Address) -> Option<Attestation> {
         self.env.invoke_contract(
             self.address,
-            &Symbol::new(self.env, "get_price"),
-            (asset,).into_val(self.env),
+            &Symbol::new(self.env, "get_attestation"),
+            (rwa_token.clone(),).into_val(self.env),
         )
     }
+
+    /// Per-asset feed config, if the oracle provides one (not part of SEP-40)
+    pub fn try_get_feed_config(&self, asset: &Address) -> Option<FeedConfig> {
+        let result = self.env.try_invoke_contract::<FeedConfig, soroban_sdk::Error>(
+            self.address,
+            &Symbol::new(self.env, "get_feed_config"),
+            (asset.clone(),).into_val(self.env),
+        );
+        match result {
+            Ok(Ok(config)) => Some(config),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -234,11 +445,16 @@ impl LendingPool {
             .instance()
             .set(&DataKey::UsdcAddress, &usdc_address);
 
-        // Initialize liquidity counters
-        e.storage().instance().set(&DataKey::TotalLiquidity, &0i128);
-        e.storage()
-            .instance()
-            .set(&DataKey::TotalLockedLiquidity, &0i128);
+        // USDC is always borrowable at the default 7% rate and 10% yield share
+        Self::cache_asset_decimals(&e, &usdc_address);
+        Self::store_borrow_asset(
+            &e,
+            &usdc_address,
+            &BorrowAssetConfig {
+                interest_rate: 700,
+                yield_share_percent: 1000,
+            },
+        );
     }
 
     /// Set the liquidation bot address (only admin)
@@ -249,67 +465,394 @@ impl LendingPool {
             .set(&DataKey::LiquidationBot, &bot_address);
     }
 
+    /// Move USDC liquidity stored under the pre-multi-asset keys to the per-asset
+    /// keys (only admin). Run once after upgrading, passing every LP with a deposit;
+    /// depositors already migrated are skipped. Open loans need `migrate_usdc_loans`.
+    #[only_role(caller, "admin")]
+    pub fn migrate_usdc_liquidity(e: Env, caller: Address, depositors: Vec<Address>) {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+
+        if let Some(total) = e
+            .storage()
+            .instance()
+            .get::<LegacyDataKey, i128>(&LegacyDataKey::TotalLiquidity)
+        {
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalLiquidity(usdc_address.clone()), &total);
+            e.storage().instance().remove(&LegacyDataKey::TotalLiquidity);
+        }
+
+        if let Some(locked) = e
+            .storage()
+            .instance()
+            .get::<LegacyDataKey, i128>(&LegacyDataKey::TotalLockedLiquidity)
+        {
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalLockedLiquidity(usdc_address.clone()), &locked);
+            e.storage().instance().remove(&LegacyDataKey::TotalLockedLiquidity);
+        }
+
+        for depositor in depositors.iter() {
+            let legacy_key = LegacyDataKey::LPDeposit(depositor.clone());
+            if let Some(deposit) = e.storage().instance().get::<LegacyDataKey, LPDeposit>(&legacy_key) {
+                e.storage()
+                    .instance()
+                    .set(&DataKey::LPDeposit(depositor, usdc_address.clone()), &deposit);
+                e.storage().instance().remove(&legacy_key);
+            }
+        }
+    }
+
+    /// Rewrite loans stored before multi-asset support as USDC loans (only admin).
+    /// Run once after upgrading, passing every borrower with an open loan; loans
+    /// already in the current format are skipped.
+    #[only_role(caller, "admin")]
+    pub fn migrate_usdc_loans(e: Env, caller: Address, borrowers: Vec<Address>) {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+
+        for borrower in borrowers.iter() {
+            let key = DataKey::Loan(borrower.clone());
+            let stored: Option<Map<Symbol, Val>> = e.storage().instance().get(&key);
+            let fields = match stored {
+                Some(fields) if !fields.contains_key(Symbol::new(&e, "borrow_asset")) => fields,
+                _ => continue,
+            };
+            let legacy = LegacyLoan::try_from_val(&e, &fields.to_val()).unwrap();
+
+            let loan = Loan {
+                borrower: legacy.borrower,
+                collaterals: legacy.collaterals,
+                borrow_asset: usdc_address.clone(),
+                principal: legacy.principal,
+                outstanding_debt: legacy.outstanding_debt,
+                interest_rate: legacy.interest_rate,
+                start_time: legacy.start_time,
+                end_time: legacy.end_time,
+                last_interest_update: legacy.last_interest_update,
+                warnings_issued: legacy.warnings_issued,
+                last_warning_time: legacy.last_warning_time,
+                penalties: legacy.penalties,
+                yield_share_percent: legacy.yield_share_percent,
+            };
+            e.storage().instance().set(&key, &loan);
+        }
+    }
+
     /// Register a vault for a specific stRWA token (only admin)
     #[only_role(caller, "admin")]
     pub fn register_vault(e: Env, caller: Address, strwa_token: Address, vault: Address) {
+        Self::cache_asset_decimals(&e, &strwa_token);
         e.storage().instance().set(&DataKey::Vaults(strwa_token), &vault);
     }
 
+    /// Query and cache the token and oracle decimals used to value an asset
+    fn cache_asset_decimals(e: &Env, token: &Address) {
+        let config: Option<OracleConfig> = e
+            .storage()
+            .instance()
+            .get(&DataKey::OracleConfig(token.clone()));
+        let price_decimals = match config {
+            Some(config) => config.primary_decimals,
+            None => {
+                let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
+                OracleClient::new(e, &oracle_address).decimals()
+            }
+        };
+        let decimals = AssetDecimals {
+            token_decimals: TokenClient::new(e, token).decimals(),
+            price_decimals,
+        };
+        e.storage()
+            .instance()
+            .set(&DataKey::AssetDecimals(token.clone()), &decimals);
+    }
+
+    /// Panic unless the RWA token behind a stRWA collateral has a recent reserve
+    /// attestation covering its full on-chain supply
+    fn require_reserve_backed(e: &Env, strwa_token: &Address) {
+        let vault_address: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Vaults(strwa_token.clone()))
+            .expect("Vault not registered for this token");
+        let rwa_token = VaultClient::new(e, &vault_address).get_rwa_token();
+
+        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
+        let attestation = OracleClient::new(e, &oracle_address)
+            .get_attestation(&rwa_token)
+            .unwrap_or_else(|| panic!("No reserve attestation for collateral"));
+
+        let now = e.ledger().timestamp();
+        let max_age = Self::get_attestation_max_age(e.clone());
+        if attestation.timestamp > now || now - attestation.timestamp > max_age {
+            panic!("Reserve attestation is stale");
+        }
+
+        if TokenClient::new(e, &rwa_token).total_supply() > attestation.reserve_amount {
+            panic!("Collateral supply exceeds attested reserve");
+        }
+    }
+
+    fn asset_decimals(e: &Env, token: &Address) -> AssetDecimals {
+        e.storage()
+            .instance()
+            .get(&DataKey::AssetDecimals(token.clone()))
+            .expect("Asset decimals not cached")
+    }
+
+    /// Set the minimum collateral ratio for a stRWA token in basis points (only admin)
+    #[only_role(caller, "admin")]
+    pub fn set_collateral_ratio(e: Env, caller: Address, strwa_token: Address, ratio_bps: i128) {
+        if ratio_bps < 10000 {
+            panic!("Collateral ratio must be at least 100%");
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::CollateralRatio(strwa_token), &ratio_bps);
+    }
+
+    /// Get the minimum collateral ratio for a stRWA token (defaults to 140%)
+    pub fn get_collateral_ratio(e: Env, strwa_token: Address) -> i128 {
+        e.storage()
+            .instance()
+            .get(&DataKey::CollateralRatio(strwa_token))
+            .unwrap_or(DEFAULT_COLLATERAL_RATIO_BPS)
+    }
+
+    /// Set primary and optional secondary oracle for an asset (only admin).
+    /// A max age set with `set_price_max_age` is kept.
+    #[only_role(caller, "admin")]
+    pub fn set_oracle_config(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        primary: Address,
+        secondary: Option<Address>,
+        max_deviation_bps: i128,
+    ) {
+        if max_deviation_bps <= 0 || max_deviation_bps > 10000 {
+            panic!("Invalid max deviation");
+        }
+
+        let primary_decimals = OracleClient::new(&e, &primary).decimals();
+        let secondary_decimals = match &secondary {
+            Some(oracle) => OracleClient::new(&e, oracle).decimals(),
+            None => 0,
+        };
+
+        let max_age = Self::get_oracle_config(e.clone(), asset.clone()).max_age;
+        let config = OracleConfig {
+            primary,
+            primary_decimals,
+            secondary,
+            secondary_decimals,
+            max_deviation_bps,
+            max_age,
+        };
+        e.storage()
+            .instance()
+            .set(&DataKey::OracleConfig(asset.clone()), &config);
+
+        // Aggregated prices are expressed in the primary oracle's decimals
+        if let Some(mut decimals) = Self::get_asset_decimals(e.clone(), asset.clone()) {
+            decimals.price_decimals = primary_decimals;
+            e.storage()
+                .instance()
+                .set(&DataKey::AssetDecimals(asset), &decimals);
+        }
+    }
+
+    /// Get the oracle configuration for an asset (defaults to the pool oracle alone)
+    pub fn get_oracle_config(e: Env, asset: Address) -> OracleConfig {
+        if let Some(config) = e.storage().instance().get(&DataKey::OracleConfig(asset.clone())) {
+            return config;
+        }
+
+        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
+        let price_decimals = Self::get_asset_decimals(e.clone(), asset.clone())
+            .map(|decimals| decimals.price_decimals)
+            .unwrap_or(0);
+        OracleConfig {
+            primary: oracle_address,
+            primary_decimals: price_decimals,
+            secondary: None,
+            secondary_decimals: 0,
+            max_deviation_bps: 10000,
+            max_age: None,
+        }
+    }
+
+    /// Override how old a price for an asset may be before it is ignored (only admin).
+    /// Without an override each oracle's own feed config applies.
+    #[only_role(caller, "admin")]
+    pub fn set_price_max_age(e: Env, caller: Address, asset: Address, max_age: u64) {
+        if max_age == 0 {
+            panic!("Max age must be positive");
+        }
+
+        let mut config = Self::get_oracle_config(e.clone(), asset.clone());
+        config.max_age = Some(max_age);
+        e.storage()
+            .instance()
+            .set(&DataKey::OracleConfig(asset), &config);
+    }
+
+    /// Max age currently published by the oracle for an asset, or one day for
+    /// oracles without per-asset feed configs (e.g. plain SEP-40 feeds)
+    fn feed_max_age(e: &Env, oracle: &Address, asset: &Address) -> u64 {
+        OracleClient::new(e, oracle)
+            .try_get_feed_config(asset)
+            .map(|config| config.max_age)
+            .unwrap_or(DEFAULT_PRICE_MAX_AGE)
+    }
+
+    /// Set what the pool allows when no reliable price is available (only admin)
+    #[only_role(caller, "admin")]
+    pub fn set_oracle_failure_mode(e: Env, caller: Address, mode: OracleFailureMode) {
+        e.storage().instance().set(&DataKey::OracleFailureMode, &mode);
+    }
+
+    pub fn get_oracle_failure_mode(e: Env) -> OracleFailureMode {
+        e.storage()
+            .instance()
+            .get(&DataKey::OracleFailureMode)
+            .unwrap_or(OracleFailureMode::RepayOnly)
+    }
+
+    /// Set how long a reserve attestation backs new collateral (only admin)
+    #[only_role(caller, "admin")]
+    pub fn set_attestation_max_age(e: Env, caller: Address, max_age: u64) {
+        if max_age == 0 {
+            panic!("Max age must be positive");
+        }
+        e.storage().instance().set(&DataKey::AttestationMaxAge, &max_age);
+    }
+
+    pub fn get_attestation_max_age(e: Env) -> u64 {
+        e.storage()
+            .instance()
+            .get(&DataKey::AttestationMaxAge)
+            .unwrap_or(DEFAULT_ATTESTATION_MAX_AGE)
+    }
+
+    /// Register or update a borrowable asset and its rate model (only admin)
+    #[only_role(caller, "admin")]
+    pub fn register_borrow_asset(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        interest_rate: i128,
+        yield_share_percent: i128,
+    ) {
+        if interest_rate < 0 || !(0..=10000).contains(&yield_share_percent) {
+            panic!("Invalid rate parameters");
+        }
+
+        Self::cache_asset_decimals(&e, &asset);
+        Self::store_borrow_asset(
+            &e,
+            &asset,
+            &BorrowAssetConfig {
+                interest_rate,
+                yield_share_percent,
+            },
+        );
+
+        e.events()
+            .publish((symbol_short!("asset_reg"),), (asset, interest_rate, yield_share_percent));
+    }
+
+    fn store_borrow_asset(e: &Env, asset: &Address, config: &BorrowAssetConfig) {
+        let mut assets: Vec<Address> = e
+            .storage()
+            .instance()
+            .get(&DataKey::BorrowAssets)
+            .unwrap_or(Vec::new(e));
+
+        if !assets.contains(asset) {
+            assets.push_back(asset.clone());
+            e.storage().instance().set(&DataKey::BorrowAssets, &assets);
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalLiquidity(asset.clone()), &0i128);
+            e.storage()
+                .instance()
+                .set(&DataKey::TotalLockedLiquidity(asset.clone()), &0i128);
+        }
+
+        e.storage()
+            .instance()
+            .set(&DataKey::BorrowAsset(asset.clone()), config);
+    }
+
+    fn borrow_asset_config(e: &Env, asset: &Address) -> BorrowAssetConfig {
+        e.storage()
+            .instance()
+            .get(&DataKey::BorrowAsset(asset.clone()))
+            .expect("Asset not borrowable")
+    }
+
     // ========================================================================
     // LP Functions
     // ========================================================================
 
     /// LP deposits USDC to earn interest
     pub fn lp_deposit(e: Env, depositor: Address, amount: i128) {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::lp_deposit_asset(e, depositor, usdc_address, amount);
+    }
+
+    /// LP deposits a borrowable asset to earn interest
+    pub fn lp_deposit_asset(e: Env, depositor: Address, asset: Address, amount: i128) {
         depositor.require_auth();
 
         if amount <= 0 {
             panic!("Amount must be positive");
         }
 
-        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-        let usdc_client = UsdcClient::new(&e, &usdc_address);
+        // Only registered assets have liquidity accounting
+        Self::borrow_asset_config(&e, &asset);
+
+        let token_client = TokenClient::new(&e, &asset);
 
-        // Transfer USDC from depositor to contract
-        usdc_client.transfer(&depositor, &e.current_contract_address(), &amount);
+        // Transfer asset from depositor to contract
+        token_client.transfer(&depositor, &e.current_contract_address(), &amount);
 
         // Update or create LP deposit record
-        let mut deposit = e
-            .storage()
-            .instance()
-            .get(&DataKey::LPDeposit(depositor.clone()))
-            .unwrap_or(LPDeposit {
-                depositor: depositor.clone(),
-                total_deposited: 0,
-                locked_amount: 0,
-                available_amount: 0,
-                total_interest_earned: 0,
-            });
+        let mut deposit = Self::get_lp_deposit_asset(e.clone(), depositor.clone(), asset.clone());
 
         deposit.total_deposited += amount;
         deposit.available_amount += amount;
 
         e.storage()
             .instance()
-            .set(&DataKey::LPDeposit(depositor.clone()), &deposit);
+            .set(&DataKey::LPDeposit(depositor.clone(), asset.clone()), &deposit);
 
         // Update total liquidity
         let mut total_liquidity: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(asset.clone()))
             .unwrap_or(0);
         total_liquidity += amount;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLiquidity, &total_liquidity);
+            .set(&DataKey::TotalLiquidity(asset.clone()), &total_liquidity);
 
         e.events()
-            .publish((symbol_short!("lp_depo"),), (depositor, amount));
+            .publish((symbol_short!("lp_depo"),), (depositor, asset, amount));
     }
 
     /// LP withdraws USDC (only available amount, not locked in loans)
     pub fn lp_withdraw(e: Env, depositor: Address, amount: i128) {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::lp_withdraw_asset(e, depositor, usdc_address, amount);
+    }
+
+    /// LP withdraws a borrowable asset (only available amount, not locked in loans)
+    pub fn lp_withdraw_asset(e: Env, depositor: Address, asset: Address, amount: i128) {
         depositor.require_auth();
 
         if amount <= 0 {
@@ -319,7 +862,7 @@ impl LendingPool {
         let mut deposit: LPDeposit = e
             .storage()
             .instance()
-            .get(&DataKey::LPDeposit(depositor.clone()))
+            .get(&DataKey::LPDeposit(depositor.clone(), asset.clone()))
             .expect("No deposit found");
 
         if amount > deposit.available_amount {
@@ -329,36 +872,41 @@ impl LendingPool {
         deposit.available_amount -= amount;
         deposit.total_deposited -= amount;
 
-        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-        let usdc_client = UsdcClient::new(&e, &usdc_address);
+        let token_client = TokenClient::new(&e, &asset);
 
-        // Transfer USDC back to depositor
-        usdc_client.transfer(&e.current_contract_address(), &depositor, &amount);
+        // Transfer asset back to depositor
+        token_client.transfer(&e.current_contract_address(), &depositor, &amount);
 
         e.storage()
             .instance()
-            .set(&DataKey::LPDeposit(depositor.clone()), &deposit);
+            .set(&DataKey::LPDeposit(depositor.clone(), asset.clone()), &deposit);
 
         // Update total liquidity
         let mut total_liquidity: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(asset.clone()))
             .unwrap();
         total_liquidity -= amount;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLiquidity, &total_liquidity);
+            .set(&DataKey::TotalLiquidity(asset.clone()), &total_liquidity);
 
         e.events()
-            .publish((symbol_short!("lp_withd"),), (depositor, amount));
+            .publish((symbol_short!("lp_withd"),), (depositor, asset, amount));
     }
 
-    /// Get LP deposit info
+    /// Get LP deposit info for USDC
     pub fn get_lp_deposit(e: Env, depositor: Address) -> LPDeposit {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::get_lp_deposit_asset(e, depositor, usdc_address)
+    }
+
+    /// Get LP deposit info for a borrowable asset
+    pub fn get_lp_deposit_asset(e: Env, depositor: Address, asset: Address) -> LPDeposit {
         e.storage()
             .instance()
-            .get(&DataKey::LPDeposit(depositor.clone()))
+            .get(&DataKey::LPDeposit(depositor.clone(), asset))
             .unwrap_or(LPDeposit {
                 depositor: depositor.clone(),
                 total_deposited: 0,
@@ -372,13 +920,26 @@ impl LendingPool {
     // Loan Origination
     // ========================================================================
 
-    /// Originate a new loan
+    /// Originate a new USDC loan
     pub fn originate_loan(
         e: Env,
         borrower: Address,
         collaterals: Vec<CollateralInput>,
         loan_amount: i128,
         duration_months: u32,
+    ) {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::originate_loan_asset(e, borrower, collaterals, usdc_address, loan_amount, duration_months);
+    }
+
+    /// Originate a new loan in any borrowable asset
+    pub fn originate_loan_asset(
+        e: Env,
+        borrower: Address,
+        collaterals: Vec<CollateralInput>,
+        borrow_asset: Address,
+        loan_amount: i128,
+        duration_months: u32,
     ) {
         borrower.require_auth();
 
@@ -392,52 +953,46 @@ impl LendingPool {
         }
 
         // Validate loan duration (3-24 months)
-        if duration_months < 3 || duration_months > 24 {
+        if !(3..=24).contains(&duration_months) {
             panic!("Loan duration must be between 3 and 24 months");
         }
 
-        // Use a fixed interest rate and yield share for now
-        let interest_rate = 700; // 7%
-        let yield_share_percent = 1000; // 10%
-
-        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
-        let oracle_client = OracleClient::new(&e, &oracle_address);
-        let current_time = e.ledger().timestamp();
-        let mut total_collateral_value = 0;
+        // Interest rate and yield share come from the borrowed asset's rate model
+        let asset_config = Self::borrow_asset_config(&e, &borrow_asset);
+        let interest_rate = asset_config.interest_rate;
+        let yield_share_percent = asset_config.yield_share_percent;
 
         if collaterals.is_empty() {
             panic!("At least one collateral is required");
         }
 
+        // Each collateral's underlying RWA must be fully backed by attested reserves
         for collateral in collaterals.iter() {
-            let (price, price_timestamp) = oracle_client.get_price(&collateral.token_address);
+            Self::require_reserve_backed(&e, &collateral.token_address);
+        }
 
-            // Check oracle price staleness (must be < 24 hours old)
-            if current_time - price_timestamp > 86400 {
-                panic!("Oracle price is stale");
-            }
+        // Borrowing power in USDC after each collateral's own ratio
+        let capacity = Self::borrow_capacity(&e, &collaterals)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
 
-            // Calculate collateral value in USDC
-            // Assumes stRWA tokens have 18 decimals and oracle price has 7 decimals, resulting in USDC value with 7 decimals
-            let collateral_value = (collateral.amount * price) / 10_i128.pow(18);
-            total_collateral_value += collateral_value;
-        }
+        // Value the loan in USDC terms through the oracle
+        let loan_value = Self::debt_value(&e, &borrow_asset, loan_amount)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
 
-        // Check 140% collateral ratio (LTV)
-        if total_collateral_value * 100 < loan_amount * 140 {
-            panic!("Insufficient collateral (140% ratio required)");
+        if loan_value > capacity {
+            panic!("Insufficient collateral for the required collateral ratio");
         }
 
         // Check sufficient liquidity in pool
         let total_liquidity: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(borrow_asset.clone()))
             .unwrap_or(0);
         let total_locked: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLockedLiquidity)
+            .get(&DataKey::TotalLockedLiquidity(borrow_asset.clone()))
             .unwrap_or(0);
         let available_liquidity = total_liquidity - total_locked;
 
@@ -460,6 +1015,7 @@ impl LendingPool {
         let loan = Loan {
             borrower: borrower.clone(),
             collaterals: collaterals.clone(),
+            borrow_asset: borrow_asset.clone(),
             principal: loan_amount,
             outstanding_debt: loan_amount,
             interest_rate,
@@ -480,12 +1036,11 @@ impl LendingPool {
         let new_locked = total_locked + loan_amount;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLockedLiquidity, &new_locked);
+            .set(&DataKey::TotalLockedLiquidity(borrow_asset.clone()), &new_locked);
 
-        // Transfer USDC loan amount to borrower
-        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-        let usdc_client = UsdcClient::new(&e, &usdc_address);
-        usdc_client.transfer(&e.current_contract_address(), &borrower, &loan_amount);
+        // Transfer loan amount of the borrowed asset to borrower
+        let token_client = TokenClient::new(&e, &borrow_asset);
+        token_client.transfer(&e.current_contract_address(), &borrower, &loan_amount);
 
         // Mark user as borrower in each vault
         let loan_period = end_time - start_time;
@@ -496,7 +1051,7 @@ impl LendingPool {
         }
 
         e.events()
-            .publish((symbol_short!("loan_orig"),), (borrower, loan_amount));
+            .publish((symbol_short!("loan_orig"),), (borrower, borrow_asset, loan_amount));
     }
 
     // ========================================================================
@@ -571,6 +1126,18 @@ impl LendingPool {
     pub fn repay_loan(e: Env, borrower: Address, amount: i128) {
         borrower.require_auth();
 
+        Self::apply_repayment(&e, &borrower, &borrower, amount);
+    }
+
+    /// Make a loan payment on behalf of a borrower (e.g. invoice debtor or guarantor).
+    /// Only the payer authorizes and funds the payment; the borrower's vault yield is not used.
+    pub fn repay_on_behalf(e: Env, payer: Address, borrower: Address, amount: i128) {
+        payer.require_auth();
+
+        Self::apply_repayment(&e, &payer, &borrower, amount);
+    }
+
+    fn apply_repayment(e: &Env, payer: &Address, borrower: &Address, amount: i128) {
         if amount <= 0 {
             panic!("Amount must be positive");
         }
@@ -584,28 +1151,22 @@ impl LendingPool {
             .get(&DataKey::Loan(borrower.clone()))
             .expect("Loan not found");
 
-        // Try to pull yield from vaults first
-        let mut yield_pulled = 0;
-        let mut amount_to_pull = amount;
-        for collateral in loan.collaterals.iter() {
-            if amount_to_pull <= 0 {
-                break;
-            }
-            let vault_address: Address = e.storage().instance().get(&DataKey::Vaults(collateral.token_address.clone())).unwrap();
-            let vault_client = VaultClient::new(&e, &vault_address);
-            let pulled = vault_client.pull_yield_for_repay(&borrower, &amount_to_pull);
-            yield_pulled += pulled;
-            amount_to_pull -= pulled;
-        }
+        Self::require_repay_allowed(e, &loan);
+
+        // Try to pull yield from vaults first (only when the borrower pays)
+        let yield_pulled = if payer == borrower {
+            Self::pull_vault_yield(e, &loan, amount)
+        } else {
+            0
+        };
 
         let remaining_payment = amount - yield_pulled;
 
         // If yield covers the full payment, process it
-        // Otherwise, borrower needs to provide the remaining
+        // Otherwise, payer needs to provide the remaining
         if remaining_payment > 0 {
-            let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-            let usdc_client = UsdcClient::new(&e, &usdc_address);
-            usdc_client.transfer(&borrower, &e.current_contract_address(), &remaining_payment);
+            let token_client = TokenClient::new(e, &loan.borrow_asset);
+            token_client.transfer(payer, &e.current_contract_address(), &remaining_payment);
         }
 
         // Calculate LP share of the payment
@@ -622,12 +1183,12 @@ impl LendingPool {
         let mut total_liquidity: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(loan.borrow_asset.clone()))
             .unwrap();
         total_liquidity += lp_share;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLiquidity, &total_liquidity);
+            .set(&DataKey::TotalLiquidity(loan.borrow_asset.clone()), &total_liquidity);
 
         // Reset warnings if significant payment made
         if principal_payment > loan.principal / 10 {
@@ -643,12 +1204,49 @@ impl LendingPool {
         e.events()
             .publish((symbol_short!("repay"),), (borrower.clone(), amount));
 
+        if payer != borrower {
+            e.events()
+                .publish((symbol_short!("repay_for"),), (payer.clone(), borrower.clone(), amount));
+        }
+
         // Check if loan is fully repaid
         if loan.outstanding_debt <= 0 {
-            Self::close_loan(e, borrower);
+            Self::close_loan(e.clone(), borrower.clone());
+        }
+    }
+
+    /// In `Frozen` mode, repayments wait until every collateral has a reliable price
+    fn require_repay_allowed(e: &Env, loan: &Loan) {
+        if Self::get_oracle_failure_mode(e.clone()) == OracleFailureMode::Frozen
+            && Self::collateral_value(e, &loan.collaterals).is_none()
+        {
+            panic!("Oracle unavailable: loan actions frozen");
         }
     }
 
+    /// Pull the borrower's claimable vault yield towards a payment.
+    /// Vault yield is paid in USDC, so nothing is pulled for loans in other assets.
+    fn pull_vault_yield(e: &Env, loan: &Loan, amount: i128) -> i128 {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        if loan.borrow_asset != usdc_address {
+            return 0;
+        }
+
+        let mut yield_pulled = 0;
+        let mut amount_to_pull = amount;
+        for collateral in loan.collaterals.iter() {
+            if amount_to_pull <= 0 {
+                break;
+            }
+            let vault_address: Address = e.storage().instance().get(&DataKey::Vaults(collateral.token_address.clone())).unwrap();
+            let vault_client = VaultClient::new(e, &vault_address);
+            let pulled = vault_client.pull_yield_for_repay(&loan.borrower, &amount_to_pull);
+            yield_pulled += pulled;
+            amount_to_pull -= pulled;
+        }
+        yield_pulled
+    }
+
     /// Close a fully repaid loan
     fn close_loan(e: Env, borrower: Address) {
         let loan: Loan = e
@@ -671,12 +1269,12 @@ impl LendingPool {
         let mut total_locked: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLockedLiquidity)
+            .get(&DataKey::TotalLockedLiquidity(loan.borrow_asset.clone()))
             .unwrap();
         total_locked -= loan.principal;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLockedLiquidity, &total_locked);
+            .set(&DataKey::TotalLockedLiquidity(loan.borrow_asset.clone()), &total_locked);
 
         // Mark user as no longer a borrower in all associated vaults
         for collateral in loan.collaterals.iter() {
@@ -706,33 +1304,31 @@ impl LendingPool {
             .get(&DataKey::Loan(borrower.clone()))
             .expect("Loan not found");
 
+        Self::require_repay_allowed(&e, &loan);
+
         // Calculate closure fee: 5% of remaining debt
         let closure_fee = (loan.outstanding_debt * 5) / 100;
         let total_payment = loan.outstanding_debt + closure_fee;
 
         // Try to pull yield first
-        let vault_address: Address = e.storage().instance().get(&DataKey::VaultAddress).unwrap();
-        let vault_client = VaultClient::new(&e, &vault_address);
-
-        let yield_pulled = vault_client.pull_yield_for_repay(&borrower, &total_payment);
+        let yield_pulled = Self::pull_vault_yield(&e, &loan, total_payment);
         let remaining_payment = total_payment - yield_pulled;
 
         if remaining_payment > 0 {
-            let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-            let usdc_client = UsdcClient::new(&e, &usdc_address);
-            usdc_client.transfer(&borrower, &e.current_contract_address(), &remaining_payment);
+            let token_client = TokenClient::new(&e, &loan.borrow_asset);
+            token_client.transfer(&borrower, &e.current_contract_address(), &remaining_payment);
         }
 
         // Add closure fee to total liquidity (benefits LPs)
         let mut total_liquidity: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(loan.borrow_asset.clone()))
             .unwrap();
         total_liquidity += closure_fee;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLiquidity, &total_liquidity);
+            .set(&DataKey::TotalLiquidity(loan.borrow_asset.clone()), &total_liquidity);
 
         e.events()
             .publish((symbol_short!("early_cl"),), (borrower.clone(), total_payment));
@@ -747,6 +1343,29 @@ impl LendingPool {
 
     /// Issue warning to borrower (can be called by anyone to trigger check)
     pub fn check_and_issue_warning(e: Env, borrower: Address) {
+        match Self::try_issue_warning(&e, &borrower) {
+            KeeperOutcome::NoLoan => panic!("Loan not found"),
+            KeeperOutcome::StalePrice => panic!("Oracle price is stale"),
+            _ => {}
+        }
+    }
+
+    /// Run the warning check for several borrowers in one transaction.
+    /// Loans that are missing or have stale prices are skipped, not aborted.
+    pub fn check_and_warn_batch(e: Env, borrowers: Vec<Address>) -> Vec<BatchOutcome> {
+        let mut outcomes = Vec::new(&e);
+        for borrower in borrowers.iter() {
+            let outcome = Self::try_issue_warning(&e, &borrower);
+            outcomes.push_back(BatchOutcome { borrower, outcome });
+        }
+        outcomes
+    }
+
+    fn try_issue_warning(e: &Env, borrower: &Address) -> KeeperOutcome {
+        if !e.storage().instance().has(&DataKey::Loan(borrower.clone())) {
+            return KeeperOutcome::NoLoan;
+        }
+
         // Update interest first
         Self::update_loan_interest(e.clone(), borrower.clone());
 
@@ -754,23 +1373,20 @@ impl LendingPool {
             .storage()
             .instance()
             .get(&DataKey::Loan(borrower.clone()))
-            .expect("Loan not found");
+            .unwrap();
 
         let current_time = e.ledger().timestamp();
 
         // Get current total collateral value
-        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
-        let oracle_client = OracleClient::new(&e, &oracle_address);
-        let mut collateral_value = 0;
-        for collateral in loan.collaterals.iter() {
-            let (price, price_timestamp) = oracle_client.get_price(&collateral.token_address);
-            if current_time - price_timestamp > 86400 {
-                panic!("Oracle price is stale");
-            }
-            collateral_value += (collateral.amount * price) / 10_i128.pow(18);
-        }
+        let collateral_value = match Self::collateral_value(e, &loan.collaterals) {
+            Some(value) => value,
+            None => return KeeperOutcome::StalePrice,
+        };
 
-        let total_debt = loan.outstanding_debt + loan.penalties;
+        let total_debt = match Self::debt_value(e, &loan.borrow_asset, loan.outstanding_debt + loan.penalties) {
+            Some(value) => value,
+            None => return KeeperOutcome::StalePrice,
+        };
 
         // Check if warning should be issued
         // Condition 1: 2 weeks since last payment/warning
@@ -782,9 +1398,15 @@ impl LendingPool {
             current_time - loan.last_warning_time
         };
 
-        let collateral_ratio = (total_debt * 100) / collateral_value;
+        let collateral_ratio = if collateral_value == 0 {
+            i128::MAX
+        } else {
+            (total_debt * 100) / collateral_value
+        };
         let should_warn = time_since_last_warning >= two_weeks || collateral_ratio >= 110;
 
+        let mut outcome = KeeperOutcome::NoAction;
+
         if should_warn && loan.warnings_issued < 2 {
             loan.warnings_issued += 1;
             loan.last_warning_time = current_time;
@@ -799,13 +1421,23 @@ impl LendingPool {
 
             e.events()
                 .publish((symbol_short!("warning"),), (borrower.clone(), loan.warnings_issued));
+
+            outcome = KeeperOutcome::Warned;
         }
 
         // If 2 warnings issued or collateral >= 110%, trigger liquidation check
         if loan.warnings_issued >= 2 || collateral_ratio >= 110 {
             e.events()
-                .publish((symbol_short!("liq_flag"),), borrower);
+                .publish((symbol_short!("liq_flag"),), borrower.clone());
+
+            outcome = if outcome == KeeperOutcome::Warned {
+                KeeperOutcome::WarnedAndFlagged
+            } else {
+                KeeperOutcome::Flagged
+            };
         }
+
+        outcome
     }
 
     // ========================================================================
@@ -814,6 +1446,30 @@ impl LendingPool {
 
     /// Liquidate a loan (only by liquidation bot)
     pub fn liquidate_loan(e: Env, caller: Address, borrower: Address) {
+        Self::require_liquidation_bot(&e, &caller);
+
+        match Self::try_liquidate(&e, &caller, &borrower) {
+            KeeperOutcome::NoLoan => panic!("Loan not found"),
+            KeeperOutcome::StalePrice => panic!("Oracle price is stale"),
+            KeeperOutcome::NoAction => panic!("Liquidation threshold not met"),
+            _ => {}
+        }
+    }
+
+    /// Liquidate several loans in one transaction (only by liquidation bot).
+    /// Loans below the liquidation threshold are skipped, not aborted.
+    pub fn liquidate_batch(e: Env, caller: Address, borrowers: Vec<Address>) -> Vec<BatchOutcome> {
+        Self::require_liquidation_bot(&e, &caller);
+
+        let mut outcomes = Vec::new(&e);
+        for borrower in borrowers.iter() {
+            let outcome = Self::try_liquidate(&e, &caller, &borrower);
+            outcomes.push_back(BatchOutcome { borrower, outcome });
+        }
+        outcomes
+    }
+
+    fn require_liquidation_bot(e: &Env, caller: &Address) {
         caller.require_auth();
 
         // Verify caller is liquidation bot
@@ -823,9 +1479,15 @@ impl LendingPool {
             .get(&DataKey::LiquidationBot)
             .expect("Liquidation bot not set");
 
-        if caller != bot_address {
+        if *caller != bot_address {
             panic!("Only liquidation bot can liquidate");
         }
+    }
+
+    fn try_liquidate(e: &Env, caller: &Address, borrower: &Address) -> KeeperOutcome {
+        if !e.storage().instance().has(&DataKey::Loan(borrower.clone())) {
+            return KeeperOutcome::NoLoan;
+        }
 
         // Update interest first
         Self::update_loan_interest(e.clone(), borrower.clone());
@@ -834,42 +1496,49 @@ impl LendingPool {
             .storage()
             .instance()
             .get(&DataKey::Loan(borrower.clone()))
-            .expect("Loan not found");
+            .unwrap();
 
         // Get current total collateral value
-        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
-        let oracle_client = OracleClient::new(&e, &oracle_address);
-        let mut collateral_value = 0;
-        let current_time = e.ledger().timestamp();
-        for collateral in loan.collaterals.iter() {
-            let (price, price_timestamp) = oracle_client.get_price(&collateral.token_address);
-            if current_time - price_timestamp > 86400 {
-                panic!("Oracle price is stale");
-            }
-            collateral_value += (collateral.amount * price) / 10_i128.pow(18);
-        }
-        
-        let total_debt = loan.outstanding_debt + loan.penalties;
+        let collateral_value = match Self::collateral_value(e, &loan.collaterals) {
+            Some(value) => value,
+            None => return KeeperOutcome::StalePrice,
+        };
+
+        let debt_in_asset = loan.outstanding_debt + loan.penalties;
+        let total_debt = match Self::debt_value(e, &loan.borrow_asset, debt_in_asset) {
+            Some(value) => value,
+            None => return KeeperOutcome::StalePrice,
+        };
 
         // Check liquidation threshold: debt >= collateral_value × 110%
         if total_debt * 100 < collateral_value * 110 {
-            panic!("Liquidation threshold not met");
+            return KeeperOutcome::NoAction;
         }
 
-        // Calculate 10% bot reward
+        // Calculate 10% bot reward, paid in the borrowed asset
         let bot_reward = (collateral_value * 10) / 100;
         let remaining_collateral = collateral_value - bot_reward;
+        let bot_reward_in_asset = match Self::to_asset_amount(e, &loan.borrow_asset, bot_reward) {
+            Some(amount) => amount,
+            None => return KeeperOutcome::StalePrice,
+        };
 
-        // Burn all stRWA collaterals
+        // Seize all stRWA collaterals: the pool hands each vault the pledged stRWA,
+        // which the vault burns before releasing the backing RWA to the admin
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         for collateral in loan.collaterals.iter() {
-            let strwa_client = StRwaClient::new(&e, &collateral.token_address);
-            strwa_client.burn(&e.current_contract_address(), &collateral.amount);
+            let vault_address: Address = e.storage().instance().get(&DataKey::Vaults(collateral.token_address.clone())).unwrap();
+            StRwaClient::new(e, &collateral.token_address).transfer(
+                &e.current_contract_address(),
+                &vault_address,
+                &collateral.amount,
+            );
+            VaultClient::new(e, &vault_address).on_collateral_seized(borrower, &collateral.amount, &admin);
         }
 
-        // Transfer bot reward in USDC
-        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
-        let usdc_client = UsdcClient::new(&e, &usdc_address);
-        usdc_client.transfer(&e.current_contract_address(), &caller, &bot_reward);
+        // Transfer bot reward
+        let token_client = TokenClient::new(e, &loan.borrow_asset);
+        token_client.transfer(&e.current_contract_address(), caller, &bot_reward_in_asset);
 
         // Repay debt to pool from remaining collateral
         let _debt_repayment = if remaining_collateral > total_debt {
@@ -882,26 +1551,153 @@ impl LendingPool {
         let mut total_locked: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLockedLiquidity)
+            .get(&DataKey::TotalLockedLiquidity(loan.borrow_asset.clone()))
             .unwrap();
         total_locked -= loan.principal;
         e.storage()
             .instance()
-            .set(&DataKey::TotalLockedLiquidity, &total_locked);
+            .set(&DataKey::TotalLockedLiquidity(loan.borrow_asset.clone()), &total_locked);
 
-        // Mark user as no longer a borrower in all associated vaults
+        // Seizure already cleared the borrower state; release LP liquidity tracking
         for collateral in loan.collaterals.iter() {
             let vault_address: Address = e.storage().instance().get(&DataKey::Vaults(collateral.token_address.clone())).unwrap();
-            let vault_client = VaultClient::new(&e, &vault_address);
-            vault_client.mark_as_borrower(&borrower, &0, &0);
-            vault_client.set_lp_liquidity_used(&borrower, &0);
+            VaultClient::new(e, &vault_address).set_lp_liquidity_used(borrower, &0);
         }
 
         // Remove loan record
         e.storage().instance().remove(&DataKey::Loan(borrower.clone()));
 
         e.events()
-            .publish((symbol_short!("liquidat"),), (borrower, total_debt));
+            .publish((symbol_short!("liquidat"),), (borrower.clone(), loan.borrow_asset, debt_in_asset));
+
+        KeeperOutcome::Liquidated
+    }
+
+    /// Value collateral in USDC, or `None` if any oracle price is stale
+    fn collateral_value(e: &Env, collaterals: &Vec<CollateralInput>) -> Option<i128> {
+        let mut total_collateral_value = 0;
+
+        for collateral in collaterals.iter() {
+            total_collateral_value += Self::asset_value(e, &collateral.token_address, collateral.amount)?;
+        }
+
+        Some(total_collateral_value)
+    }
+
+    /// Maximum debt the collaterals can carry, using each asset's collateral ratio
+    fn borrow_capacity(e: &Env, collaterals: &Vec<CollateralInput>) -> Option<i128> {
+        let mut capacity = 0;
+
+        for collateral in collaterals.iter() {
+            let value = Self::asset_value(e, &collateral.token_address, collateral.amount)?;
+            let ratio_bps = Self::get_collateral_ratio(e.clone(), collateral.token_address.clone());
+            capacity += (value * 10000) / ratio_bps;
+        }
+
+        Some(capacity)
+    }
+
+    /// Value an amount of a single asset in USDC, or `None` if no reliable price is available
+    fn asset_value(e: &Env, token: &Address, amount: i128) -> Option<i128> {
+        let price = Self::resolve_price(e, token)?;
+        Some(Self::normalize_value(amount, price, &Self::asset_decimals(e, token)))
+    }
+
+    /// Convert `amount` of a token at `price` into a USDC value with `VALUE_DECIMALS`.
+    /// This is the single place where token and oracle decimals are reconciled.
+    fn normalize_value(amount: i128, price: i128, decimals: &AssetDecimals) -> i128 {
+        let scale = decimals.token_decimals + decimals.price_decimals;
+        if scale >= VALUE_DECIMALS {
+            (amount * price) / 10_i128.pow(scale - VALUE_DECIMALS)
+        } else {
+            amount * price * 10_i128.pow(VALUE_DECIMALS - scale)
+        }
+    }
+
+    /// Inverse of `normalize_value`: the token amount worth `value` USDC at `price`
+    fn denormalize_value(value: i128, price: i128, decimals: &AssetDecimals) -> i128 {
+        let scale = decimals.token_decimals + decimals.price_decimals;
+        if scale >= VALUE_DECIMALS {
+            (value * 10_i128.pow(scale - VALUE_DECIMALS)) / price
+        } else {
+            value / (price * 10_i128.pow(VALUE_DECIMALS - scale))
+        }
+    }
+
+    /// Value an amount of a borrowable asset in USDC, or `None` if its oracle price is stale.
+    /// USDC itself is the unit of account and is never priced through the oracle.
+    fn debt_value(e: &Env, asset: &Address, amount: i128) -> Option<i128> {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        if *asset == usdc_address {
+            return Some(amount);
+        }
+
+        let price = Self::resolve_price(e, asset)?;
+        Some(Self::normalize_value(amount, price, &Self::asset_decimals(e, asset)))
+    }
+
+    /// Convert a USDC value into an amount of a borrowable asset
+    fn to_asset_amount(e: &Env, asset: &Address, value: i128) -> Option<i128> {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        if *asset == usdc_address {
+            return Some(value);
+        }
+
+        let price = Self::resolve_price(e, asset)?;
+        Some(Self::denormalize_value(value, price, &Self::asset_decimals(e, asset)))
+    }
+
+    /// Aggregate the primary and secondary oracle into one price, expressed in the
+    /// primary oracle's decimals. Falls back to the secondary when the primary is stale,
+    /// and returns `None` when both fail or they disagree by more than the allowed deviation.
+    fn resolve_price(e: &Env, asset: &Address) -> Option<i128> {
+        let config = Self::get_oracle_config(e.clone(), asset.clone());
+
+        let primary_price = Self::fresh_price(e, &config.primary, asset, config.max_age);
+        let secondary_price = config
+            .secondary
+            .as_ref()
+            .and_then(|secondary| Self::fresh_price(e, secondary, asset, config.max_age))
+            .map(|price| Self::rescale_price(price, config.secondary_decimals, config.primary_decimals));
+
+        match (primary_price, secondary_price) {
+            (Some(primary), Some(secondary)) => {
+                let deviation_bps = ((primary - secondary).abs() * 10000) / primary;
+                if deviation_bps > config.max_deviation_bps {
+                    e.events()
+                        .publish((symbol_short!("oracle_dv"), asset.clone()), (primary, secondary));
+                    return None;
+                }
+                Some(primary)
+            }
+            (Some(primary), None) => Some(primary),
+            (None, Some(secondary)) => {
+                e.events()
+                    .publish((symbol_short!("oracle_fb"), asset.clone()), secondary);
+                Some(secondary)
+            }
+            (None, None) => None,
+        }
+    }
+
+    fn rescale_price(price: i128, from_decimals: u32, to_decimals: u32) -> i128 {
+        if from_decimals >= to_decimals {
+            price / 10_i128.pow(from_decimals - to_decimals)
+        } else {
+            price * 10_i128.pow(to_decimals - from_decimals)
+        }
+    }
+
+    /// Latest price from one oracle, or `None` if missing, timestamped in the future
+    /// or older than the pool override or else the oracle's current max age
+    fn fresh_price(e: &Env, oracle: &Address, asset: &Address, max_age: Option<u64>) -> Option<i128> {
+        let price_data = OracleClient::new(e, oracle).lastprice(asset)?;
+        let max_age = max_age.unwrap_or_else(|| Self::feed_max_age(e, oracle, asset));
+        let now = e.ledger().timestamp();
+        if price_data.price <= 0 || price_data.timestamp > now || now - price_data.timestamp > max_age {
+            return None;
+        }
+        Some(price_data.price)
     }
 
     // ========================================================================
@@ -909,8 +1705,14 @@ impl LendingPool {
     // ========================================================================
 
     fn add_to_collateral(collaterals: &mut Vec<CollateralInput>, change: &CollateralChange) {
-        if let Some(collateral) = collaterals.iter_mut().find(|c| c.token_address == change.token_address) {
+        let index = collaterals
+            .iter()
+            .position(|c| c.token_address == change.token_address)
+            .map(|i| i as u32);
+        if let Some(i) = index {
+            let mut collateral = collaterals.get(i).unwrap();
             collateral.amount += change.amount;
+            collaterals.set(i, collateral);
         } else {
             collaterals.push_back(CollateralInput {
                 token_address: change.token_address.clone(),
@@ -920,7 +1722,10 @@ impl LendingPool {
     }
 
     fn remove_from_collateral(collaterals: &mut Vec<CollateralInput>, change: &CollateralChange) {
-        let index = collaterals.iter().position(|c| c.token_address == change.token_address);
+        let index = collaterals
+            .iter()
+            .position(|c| c.token_address == change.token_address)
+            .map(|i| i as u32);
         if let Some(i) = index {
             let mut collateral = collaterals.get(i).unwrap();
             if collateral.amount < change.amount {
@@ -930,34 +1735,13 @@ impl LendingPool {
             if collateral.amount == 0 {
                 collaterals.remove(i);
             } else {
-                 collaterals.set(i, collateral);
+                collaterals.set(i, collateral);
             }
         } else {
             panic!("Collateral not found");
         }
     }
 
-    fn calculate_health_factor(e: &Env, loan: &Loan) -> u128 {
-        let oracle_address: Address = e.storage().instance().get(&DataKey::OracleAddress).unwrap();
-        let oracle_client = OracleClient::new(e, &oracle_address);
-        let mut total_collateral_value = 0;
-        let current_time = e.ledger().timestamp();
-
-        for collateral in loan.collaterals.iter() {
-            let (price, price_timestamp) = oracle_client.get_price(&collateral.token_address);
-            if current_time - price_timestamp > 86400 {
-                panic!("Oracle price is stale");
-            }
-            total_collateral_value += (collateral.amount * price) / 10_i128.pow(18);
-        }
-
-        if loan.outstanding_debt == 0 {
-            return u128::MAX;
-        }
-
-        (total_collateral_value as u128 * 100) / loan.outstanding_debt as u128
-    }
-
     pub fn adjust_collateral(
         e: Env,
         borrower: Address,
@@ -965,6 +1749,9 @@ impl LendingPool {
     ) {
         borrower.require_auth();
 
+        // Update interest first
+        Self::update_loan_interest(e.clone(), borrower.clone());
+
         let mut loan: Loan = e
             .storage()
             .instance()
@@ -974,6 +1761,7 @@ impl LendingPool {
         for change in collateral_changes.iter() {
             match change.action {
                 Action::Add => {
+                    Self::require_reserve_backed(&e, &change.token_address);
                     let token = StRwaClient::new(&e, &change.token_address);
                     token.transfer_from(&e.current_contract_address(), &borrower, &e.current_contract_address(), &change.amount);
                     Self::add_to_collateral(&mut loan.collaterals, &change);
@@ -986,14 +1774,119 @@ impl LendingPool {
             }
         }
 
-        let new_health_factor = Self::calculate_health_factor(&e, &loan);
-        if new_health_factor < 140 {
+        // Validate LTV against each asset's collateral ratio
+        let capacity = Self::borrow_capacity(&e, &loan.collaterals)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
+        let debt_value = Self::debt_value(&e, &loan.borrow_asset, loan.outstanding_debt + loan.penalties)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
+        if debt_value > capacity {
             panic!("Insufficient collateral after adjustment");
         }
 
         e.storage().instance().set(&DataKey::Loan(borrower.clone()), &loan);
     }
 
+    /// Move collateral from one stRWA asset class to another within an open loan
+    pub fn swap_collateral(
+        e: Env,
+        borrower: Address,
+        remove: CollateralInput,
+        add: CollateralInput,
+    ) {
+        borrower.require_auth();
+
+        if remove.amount <= 0 || add.amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        if remove.token_address == add.token_address {
+            panic!("Cannot swap collateral with itself");
+        }
+
+        let add_vault: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Vaults(add.token_address.clone()))
+            .expect("Vault not registered for this token");
+
+        // The incoming collateral must be backed like any newly pledged collateral
+        Self::require_reserve_backed(&e, &add.token_address);
+
+        // Update interest first
+        Self::update_loan_interest(e.clone(), borrower.clone());
+
+        let mut loan: Loan = e
+            .storage()
+            .instance()
+            .get(&DataKey::Loan(borrower.clone()))
+            .expect("Loan not found");
+
+        let already_pledged = loan
+            .collaterals
+            .iter()
+            .any(|c| c.token_address == add.token_address);
+
+        Self::remove_from_collateral(
+            &mut loan.collaterals,
+            &CollateralChange {
+                action: Action::Remove,
+                token_address: remove.token_address.clone(),
+                amount: remove.amount,
+            },
+        );
+        Self::add_to_collateral(
+            &mut loan.collaterals,
+            &CollateralChange {
+                action: Action::Add,
+                token_address: add.token_address.clone(),
+                amount: add.amount,
+            },
+        );
+
+        // Validate LTV against each asset's collateral ratio
+        let capacity = Self::borrow_capacity(&e, &loan.collaterals)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
+        let debt_value = Self::debt_value(&e, &loan.borrow_asset, loan.outstanding_debt + loan.penalties)
+            .unwrap_or_else(|| panic!("Oracle price is stale"));
+        if debt_value > capacity {
+            panic!("Insufficient collateral after swap");
+        }
+
+        // Move tokens: new collateral in, old collateral out
+        let add_token = StRwaClient::new(&e, &add.token_address);
+        add_token.transfer(&borrower, &e.current_contract_address(), &add.amount);
+
+        let remove_token = StRwaClient::new(&e, &remove.token_address);
+        remove_token.transfer(&e.current_contract_address(), &borrower, &remove.amount);
+
+        e.storage().instance().set(&DataKey::Loan(borrower.clone()), &loan);
+
+        // Update borrower markers in both vaults. A vault already backing the loan
+        // keeps its lockup; a newly pledged one is locked for the rest of the loan.
+        if !already_pledged {
+            let remaining_period = loan.end_time.saturating_sub(e.ledger().timestamp());
+            VaultClient::new(&e, &add_vault).mark_as_borrower(&borrower, &loan.principal, &remaining_period);
+        }
+
+        let still_pledged = loan
+            .collaterals
+            .iter()
+            .any(|c| c.token_address == remove.token_address);
+        if !still_pledged {
+            let remove_vault: Address = e
+                .storage()
+                .instance()
+                .get(&DataKey::Vaults(remove.token_address.clone()))
+                .unwrap();
+            VaultClient::new(&e, &remove_vault).update_borrowed_amount(&borrower, &0);
+        }
+
+        e.events().publish(
+            (symbol_short!("col_swap"),),
+            (borrower, remove.token_address, add.token_address),
+        );
+    }
+
     // ========================================================================
     // View Functions
     // ========================================================================
@@ -1002,32 +1895,51 @@ impl LendingPool {
         e.storage().instance().get(&DataKey::Loan(borrower))
     }
 
-    pub fn get_token_risk_profile(e: Env, rwa_token: Address) -> Option<TokenRiskProfile> {
-        e.storage()
-            .instance()
-            .get(&DataKey::TokenRiskProfile(rwa_token))
+    pub fn get_total_liquidity(e: Env) -> i128 {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::get_asset_total_liquidity(e, usdc_address)
     }
 
-    pub fn get_total_liquidity(e: Env) -> i128 {
+    pub fn get_available_liquidity(e: Env) -> i128 {
+        let usdc_address: Address = e.storage().instance().get(&DataKey::UsdcAddress).unwrap();
+        Self::get_asset_available_liquidity(e, usdc_address)
+    }
+
+    pub fn get_asset_total_liquidity(e: Env, asset: Address) -> i128 {
         e.storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(asset))
             .unwrap_or(0)
     }
 
-    pub fn get_available_liquidity(e: Env) -> i128 {
+    pub fn get_asset_available_liquidity(e: Env, asset: Address) -> i128 {
         let total: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLiquidity)
+            .get(&DataKey::TotalLiquidity(asset.clone()))
             .unwrap_or(0);
         let locked: i128 = e
             .storage()
             .instance()
-            .get(&DataKey::TotalLockedLiquidity)
+            .get(&DataKey::TotalLockedLiquidity(asset))
             .unwrap_or(0);
         total - locked
     }
+
+    pub fn get_borrow_assets(e: Env) -> Vec<Address> {
+        e.storage()
+            .instance()
+            .get(&DataKey::BorrowAssets)
+            .unwrap_or(Vec::new(&e))
+    }
+
+    pub fn get_borrow_asset(e: Env, asset: Address) -> Option<BorrowAssetConfig> {
+        e.storage().instance().get(&DataKey::BorrowAsset(asset))
+    }
+
+    pub fn get_asset_decimals(e: Env, token: Address) -> Option<AssetDecimals> {
+        e.storage().instance().get(&DataKey::AssetDecimals(token))
+    }
 }
 
 // ============================================================================
@@ -1,15 +1,22 @@
 #![cfg(test)]
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
-
-use crate::contract::{LendingPool, LendingPoolClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+use crate::contract::{
+    Action, Asset, Attestation, CollateralChange, CollateralInput, DataKey, FeedConfig,
+    KeeperOutcome, LPDeposit, LegacyDataKey, LegacyLoan, LendingPool, LendingPoolClient,
+    OracleFailureMode, PriceData,
+};
 use mock_rwa_token::contract::{MockRwaToken, MockRwaTokenClient};
 use rwa_vault::contract::{RwaVault, RwaVaultClient};
 use strwa_token::contract::{StRwaToken, StRwaTokenClient};
 use usdc_mock::contract::{UsdcMock, UsdcMockClient};
 
-// Mock Oracle for testing
+// Mock SEP-40 Oracle for testing
 use soroban_sdk::{contract, contractimpl};
 
 #[contract]
@@ -17,16 +24,81 @@ pub struct MockOracle;
 
 #[contractimpl]
 impl MockOracle {
-    pub fn get_price(e: Env, _asset: Address) -> (i128, u64) {
-        // Return price of 1 USDC per stRWA token and current ledger timestamp
-        (1_000_000i128, e.ledger().timestamp()) // Price with 6 decimals, timestamp
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        // Return price of 1 USDC per token unless a test sets one, at the
+        // current ledger timestamp unless a test pins an older one
+        let price = match asset {
+            Asset::Stellar(address) => e
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&e, "price"), address))
+                .unwrap_or(1_000_000i128), // Price with 6 decimals
+            Asset::Other(_) => 1_000_000i128,
+        };
+        let timestamp = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "price_ts"))
+            .unwrap_or(e.ledger().timestamp());
+        Some(PriceData { price, timestamp })
+    }
+
+    pub fn set_price(e: Env, asset: Address, price: i128) {
+        e.storage().instance().set(&(Symbol::new(&e, "price"), asset), &price);
+    }
+
+    pub fn get_feed_config(e: Env, asset: Address) -> FeedConfig {
+        // Behaves like a plain SEP-40 oracle until a test sets a max age
+        let max_age: u64 = e
+            .storage()
+            .instance()
+            .get(&(Symbol::new(&e, "max_age"), asset))
+            .expect("No feed config");
+        FeedConfig { max_age }
+    }
+
+    pub fn set_max_age(e: Env, asset: Address, max_age: u64) {
+        e.storage().instance().set(&(Symbol::new(&e, "max_age"), asset), &max_age);
+    }
+
+    pub fn decimals(_e: Env) -> u32 {
+        6
+    }
+
+    pub fn set_price_timestamp(e: Env, timestamp: u64) {
+        e.storage().instance().set(&Symbol::new(&e, "price_ts"), &timestamp);
+    }
+
+    pub fn set_reserve(e: Env, rwa_token: Address, reserve_amount: i128) {
+        e.storage().instance().set(&rwa_token, &reserve_amount);
+    }
+
+    pub fn get_attestation(e: Env, rwa_token: Address) -> Option<Attestation> {
+        // Fully backed unless a test sets a lower reserve
+        let reserve_amount: i128 = e.storage().instance().get(&rwa_token).unwrap_or(i128::MAX);
+        let timestamp = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "attest_ts"))
+            .unwrap_or(e.ledger().timestamp());
+        Some(Attestation {
+            nav: 1_000_000,
+            reserve_amount,
+            custodian_id: Symbol::new(&e, "custodian"),
+            report_hash: BytesN::from_array(&e, &[0u8; 32]),
+            timestamp,
+        })
     }
 
-    pub fn set_price(_e: Env, _asset: Address, _price: i128, _timestamp: u64) {
-        // For test manipulation if needed
+    pub fn set_attestation_timestamp(e: Env, timestamp: u64) {
+        e.storage().instance().set(&Symbol::new(&e, "attest_ts"), &timestamp);
     }
 }
 
+/// stRWA has 18 decimals and USDC 7, so at the mock oracle's 1:1 price this
+/// many stRWA base units are worth one USDC base unit
+const COLLATERAL_SCALE: i128 = 100_000_000_000;
+
 struct TestSetup<'a> {
     env: Env,
     admin: Address,
@@ -55,7 +127,10 @@ fn setup_test<'a>() -> TestSetup<'a> {
     let usdc_client = UsdcMockClient::new(&env, &usdc_id);
 
     // Deploy RWA token
-    let rwa_token_id = env.register(MockRwaToken, (&admin, &admin, &10_000_000_000i128));
+    let rwa_token_id = env.register(
+        MockRwaToken,
+        (&admin, &admin, &(10_000_000 * COLLATERAL_SCALE)),
+    );
     let rwa_token_client = MockRwaTokenClient::new(&env, &rwa_token_id);
 
     // Deploy stRWA token
@@ -79,21 +154,12 @@ fn setup_test<'a>() -> TestSetup<'a> {
     let lending_pool_id = env.register(LendingPool, ());
     let lending_pool_client = LendingPoolClient::new(&env, &lending_pool_id);
 
-    lending_pool_client.initialize(
-        &admin,
-        &vault_id,
-        &oracle_id,
-        &usdc_id,
-        &strwa_token_id,
-        &rwa_token_id,
-    );
+    lending_pool_client.initialize(&admin, &oracle_id, &usdc_id);
+    lending_pool_client.register_vault(&admin, &strwa_token_id, &vault_id);
 
     // Set liquidation bot
     lending_pool_client.set_liquidation_bot(&admin, &liquidation_bot);
 
-    // Set token risk profile (low risk: 5% APR)
-    lending_pool_client.update_token_risk_profile(&admin, &rwa_token_id, &500, &(365 * 24 * 60 * 60));
-
     // Whitelist vault in RWA token
     rwa_token_client.allow_user(&vault_id, &admin);
     rwa_token_client.allow_user(&borrower, &admin);
@@ -105,7 +171,7 @@ fn setup_test<'a>() -> TestSetup<'a> {
     // Fund test users
     usdc_client.transfer(&admin, &lp_user, &1_000_000i128);
     usdc_client.transfer(&admin, &borrower, &100_000i128);
-    rwa_token_client.transfer(&admin, &borrower, &1_000_000i128);
+    rwa_token_client.transfer(&admin, &borrower, &(1_000_000 * COLLATERAL_SCALE));
 
     TestSetup {
         env,
@@ -122,6 +188,34 @@ fn setup_test<'a>() -> TestSetup<'a> {
     }
 }
 
+/// LP deposits USDC into the pool
+fn provide_liquidity(setup: &TestSetup, amount: i128) {
+    setup.lending_pool_client.lp_deposit(&setup.lp_user, &amount);
+}
+
+/// Borrower stakes enough RWA to hold `value` USDC worth of stRWA
+fn stake_collateral(setup: &TestSetup, value: i128) -> i128 {
+    let amount = value * COLLATERAL_SCALE;
+    setup.rwa_token_client.approve(
+        &setup.borrower,
+        &setup.vault_client.address,
+        &amount,
+        &100,
+    );
+    setup.vault_client.stake(&setup.borrower, &amount);
+    amount
+}
+
+fn strwa_collateral(setup: &TestSetup, amount: i128) -> Vec<CollateralInput> {
+    vec![
+        &setup.env,
+        CollateralInput {
+            token_address: setup.strwa_token_client.address.clone(),
+            amount,
+        },
+    ]
+}
+
 #[test]
 fn test_initialization() {
     let _ = setup_test();
@@ -132,13 +226,7 @@ fn test_lp_deposit() {
     let setup = setup_test();
 
     // LP deposits USDC
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &500_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &500_000);
+    provide_liquidity(&setup, 500_000);
 
     // Verify deposit
     let deposit = setup.lending_pool_client.get_lp_deposit(&setup.lp_user);
@@ -155,13 +243,7 @@ fn test_lp_withdraw() {
     let setup = setup_test();
 
     // LP deposits
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &500_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &500_000);
+    provide_liquidity(&setup, 500_000);
 
     // LP withdraws part
     setup.lending_pool_client.lp_withdraw(&setup.lp_user, &200_000);
@@ -177,55 +259,30 @@ fn test_lp_withdraw() {
 fn test_lp_withdraw_more_than_available() {
     let setup = setup_test();
 
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &500_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &500_000);
+    provide_liquidity(&setup, 500_000);
 
     // Try to withdraw more than deposited
     setup.lending_pool_client.lp_withdraw(&setup.lp_user, &600_000);
 }
 
 #[test]
-fn test_originate_loan_low_risk() {
+fn test_originate_loan() {
     let setup = setup_test();
 
     // LP provides liquidity
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
+    provide_liquidity(&setup, 1_000_000);
 
     // Borrower stakes RWA to get stRWA collateral
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &200_000);
+    let collateral = stake_collateral(&setup, 200_000);
 
     // Borrower originates loan (need 140% collateral)
-    // With 200_000 stRWA at 1:1 price, can borrow max ~142_857 USDC
+    // With 200_000 USDC of stRWA at 1:1 price, can borrow max ~142_857 USDC
     // Let's borrow 100_000 USDC
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
-
     let borrower_usdc_before = setup.usdc_client.balance(&setup.borrower);
 
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000, // collateral
+        &strwa_collateral(&setup, collateral),
         &100_000, // loan amount
         &12,      // 12 months
     );
@@ -236,9 +293,9 @@ fn test_originate_loan_low_risk() {
         .get_loan(&setup.borrower)
         .expect("Loan should exist");
     assert_eq!(loan.principal, 100_000);
-    assert_eq!(loan.collateral_amount, 200_000);
-    assert_eq!(loan.interest_rate, 700); // 7% for low risk
-    assert_eq!(loan.yield_share_percent, 1000); // 10% for low risk
+    assert_eq!(loan.collaterals, strwa_collateral(&setup, collateral));
+    assert_eq!(loan.interest_rate, 700); // USDC default rate model
+    assert_eq!(loan.yield_share_percent, 1000);
 
     // Verify USDC transferred to borrower
     let borrower_usdc_after = setup.usdc_client.balance(&setup.borrower);
@@ -247,54 +304,8 @@ fn test_originate_loan_low_risk() {
     // Verify stRWA collateral locked
     assert_eq!(
         setup.strwa_token_client.balance(&setup.lending_pool_client.address),
-        200_000
-    );
-}
-
-#[test]
-fn test_originate_loan_high_risk() {
-    let setup = setup_test();
-
-    // Update token risk profile to high risk (< 5% APR)
-    setup
-        .lending_pool_client
-        .update_token_risk_profile(&setup.admin, &setup.rwa_token_client.address, &400, &(365 * 24 * 60 * 60));
-
-    // LP provides liquidity
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    // Borrower gets collateral
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
+        collateral
     );
-    setup.vault_client.stake(&setup.borrower, &200_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
-
-    setup.lending_pool_client.originate_loan(
-        &setup.borrower,
-        &200_000,
-        &100_000,
-        &12,
-    );
-
-    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
-    assert_eq!(loan.interest_rate, 1400); // 14% for high risk
-    assert_eq!(loan.yield_share_percent, 2000); // 20% for high risk
 }
 
 #[test]
@@ -302,34 +313,14 @@ fn test_originate_loan_high_risk() {
 fn test_originate_loan_insufficient_collateral() {
     let setup = setup_test();
 
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &100_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &100_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &100_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 100_000);
 
     // Try to borrow too much (need 140% collateral)
     // With 100k collateral, max borrow is ~71k
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &100_000,
+        &strwa_collateral(&setup, collateral),
         &100_000, // Too much!
         &12,
     );
@@ -340,33 +331,13 @@ fn test_originate_loan_insufficient_collateral() {
 fn test_one_loan_per_user() {
     let setup = setup_test();
 
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &400_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &400_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &400_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 400_000);
 
     // First loan
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral / 2),
         &100_000,
         &12,
     );
@@ -374,7 +345,7 @@ fn test_one_loan_per_user() {
     // Try second loan - should fail
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral / 2),
         &100_000,
         &12,
     );
@@ -385,33 +356,13 @@ fn test_one_loan_per_user() {
 fn test_loan_duration_too_short() {
     let setup = setup_test();
 
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &200_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
 
     // 2 months - too short
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral),
         &100_000,
         &2,
     );
@@ -422,43 +373,17 @@ fn test_repay_loan() {
     let setup = setup_test();
 
     // Setup loan
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &200_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
 
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral),
         &100_000,
         &12,
     );
 
     // Repay part of loan
-    setup.usdc_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &50_000,
-        &100,
-    );
     setup.lending_pool_client.repay_loan(&setup.borrower, &50_000);
 
     // Verify debt reduced (accounting for LP share)
@@ -472,51 +397,25 @@ fn test_early_loan_closure() {
     let setup = setup_test();
 
     // Setup loan
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &200_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
 
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral),
         &100_000,
         &12,
     );
 
     // Early closure with 5% fee
     // Total payment = 100_000 + 5_000 = 105_000
-    setup.usdc_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &105_000,
-        &100,
-    );
     setup.lending_pool_client.close_loan_early(&setup.borrower);
 
     // Verify loan closed
     assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_none());
 
     // Verify collateral returned
-    assert_eq!(setup.strwa_token_client.balance(&setup.borrower), 200_000);
+    assert_eq!(setup.strwa_token_client.balance(&setup.borrower), collateral);
 }
 
 #[test]
@@ -524,32 +423,12 @@ fn test_check_and_issue_warning() {
     let setup = setup_test();
 
     // Setup loan
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
-
-    setup.rwa_token_client.approve(
-        &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
-    );
-    setup.vault_client.stake(&setup.borrower, &200_000);
-
-    setup.strwa_token_client.approve(
-        &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
-    );
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
 
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral),
         &100_000,
         &12,
     );
@@ -570,52 +449,951 @@ fn test_get_available_liquidity() {
     let setup = setup_test();
 
     // LP deposits
-    setup.usdc_client.approve(
-        &setup.lp_user,
-        &setup.lending_pool_client.address,
-        &1_000_000,
-        &100,
-    );
-    setup.lending_pool_client.lp_deposit(&setup.lp_user, &1_000_000);
+    provide_liquidity(&setup, 1_000_000);
 
     assert_eq!(setup.lending_pool_client.get_available_liquidity(), 1_000_000);
 
     // Create loan
-    setup.rwa_token_client.approve(
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &setup.vault_client.address,
-        &200_000,
-        &100,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
     );
-    setup.vault_client.stake(&setup.borrower, &200_000);
 
-    setup.strwa_token_client.approve(
+    // Available should be reduced
+    assert_eq!(setup.lending_pool_client.get_available_liquidity(), 900_000);
+}
+
+#[test]
+fn test_check_and_warn_batch_skips_missing_loans() {
+    let setup = setup_test();
+
+    let other = Address::generate(&setup.env);
+    let outcomes = setup
+        .lending_pool_client
+        .check_and_warn_batch(&vec![&setup.env, setup.borrower.clone(), other.clone()]);
+
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes.get(0).unwrap().borrower, setup.borrower);
+    assert_eq!(outcomes.get(0).unwrap().outcome, KeeperOutcome::NoLoan);
+    assert_eq!(outcomes.get(1).unwrap().borrower, other);
+    assert_eq!(outcomes.get(1).unwrap().outcome, KeeperOutcome::NoLoan);
+}
+
+#[test]
+fn test_second_warning_reports_flag() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &setup.lending_pool_client.address,
-        &200_000,
-        &100,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+
+    let borrowers = vec![&setup.env, setup.borrower.clone()];
+    let two_weeks = 14 * 24 * 60 * 60;
+
+    setup.env.ledger().with_mut(|l| l.timestamp += two_weeks);
+    let outcomes = setup.lending_pool_client.check_and_warn_batch(&borrowers);
+    assert_eq!(outcomes.get(0).unwrap().outcome, KeeperOutcome::Warned);
+
+    // The second warning also flags the loan for liquidation
+    setup.env.ledger().with_mut(|l| l.timestamp += two_weeks);
+    let outcomes = setup.lending_pool_client.check_and_warn_batch(&borrowers);
+    assert_eq!(outcomes.get(0).unwrap().outcome, KeeperOutcome::WarnedAndFlagged);
+
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert_eq!(loan.warnings_issued, 2);
+}
+
+#[test]
+fn test_liquidate_batch_skips_healthy_loans() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+
+    let other = Address::generate(&setup.env);
+    let outcomes = setup.lending_pool_client.liquidate_batch(
+        &setup.liquidation_bot,
+        &vec![&setup.env, setup.borrower.clone(), other],
     );
 
+    assert_eq!(outcomes.get(0).unwrap().outcome, KeeperOutcome::NoAction);
+    assert_eq!(outcomes.get(1).unwrap().outcome, KeeperOutcome::NoLoan);
+    assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_some());
+}
+
+#[test]
+fn test_liquidate_batch_seizes_collateral() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
     setup.lending_pool_client.originate_loan(
         &setup.borrower,
-        &200_000,
+        &strwa_collateral(&setup, collateral),
         &100_000,
         &12,
     );
 
-    // Available should be reduced
-    assert_eq!(setup.lending_pool_client.get_available_liquidity(), 900_000);
+    // stRWA falls to 0.4 USDC: 80_000 of collateral against 100_000 of debt
+    MockOracleClient::new(&setup.env, &setup.oracle_client)
+        .set_price(&setup.strwa_token_client.address, &400_000);
+
+    // Only the bot signs; the pool, vault and stRWA authorize their own calls
+    let borrowers = vec![&setup.env, setup.borrower.clone()];
+    setup.env.mock_auths(&[MockAuth {
+        address: &setup.liquidation_bot,
+        invoke: &MockAuthInvoke {
+            contract: &setup.lending_pool_client.address,
+            fn_name: "liquidate_batch",
+            args: (setup.liquidation_bot.clone(), borrowers.clone()).into_val(&setup.env),
+            sub_invokes: &[],
+        },
+    }]);
+
+    let admin_rwa = setup.rwa_token_client.balance(&setup.admin);
+    let outcomes = setup
+        .lending_pool_client
+        .liquidate_batch(&setup.liquidation_bot, &borrowers);
+
+    assert_eq!(outcomes.get(0).unwrap().outcome, KeeperOutcome::Liquidated);
+    assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_none());
+
+    // The seized stRWA is burned and its RWA released to the admin
+    assert_eq!(setup.strwa_token_client.total_supply(), 0);
+    assert_eq!(setup.rwa_token_client.balance(&setup.admin), admin_rwa + collateral);
+
+    // 10% of the collateral value goes to the bot
+    assert_eq!(setup.usdc_client.balance(&setup.liquidation_bot), 8_000);
+}
+
+#[test]
+#[should_panic(expected = "Only liquidation bot can liquidate")]
+fn test_liquidate_batch_only_bot() {
+    let setup = setup_test();
+
+    setup
+        .lending_pool_client
+        .liquidate_batch(&setup.admin, &vec![&setup.env, setup.borrower.clone()]);
+}
+
+#[test]
+fn test_repay_on_behalf() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+
+    // Invoice debtor pays part of the loan directly
+    let payer = Address::generate(&setup.env);
+    setup.usdc_client.transfer(&setup.admin, &payer, &50_000);
+
+    let borrower_usdc_before = setup.usdc_client.balance(&setup.borrower);
+    setup
+        .lending_pool_client
+        .repay_on_behalf(&payer, &setup.borrower, &50_000);
+
+    // Payer funded the payment, borrower balance untouched
+    assert_eq!(setup.usdc_client.balance(&payer), 0);
+    assert_eq!(setup.usdc_client.balance(&setup.borrower), borrower_usdc_before);
+
+    // 50k payment with 10% LP share = 45k principal payment
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert_eq!(loan.outstanding_debt, 100_000 - 45_000);
 }
 
 #[test]
-fn test_token_risk_profile() {
+fn test_collateral_ratio_defaults_and_updates() {
     let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    assert_eq!(setup.lending_pool_client.get_collateral_ratio(&strwa), 14000);
 
-    let profile = setup
+    setup
         .lending_pool_client
-        .get_token_risk_profile(&setup.rwa_token_client.address)
-        .expect("Profile should exist");
+        .set_collateral_ratio(&setup.admin, &strwa, &12000);
+    assert_eq!(setup.lending_pool_client.get_collateral_ratio(&strwa), 12000);
+}
+
+#[test]
+#[should_panic(expected = "Cannot swap collateral with itself")]
+fn test_swap_collateral_same_token() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    setup.lending_pool_client.swap_collateral(
+        &setup.borrower,
+        &CollateralInput { token_address: strwa.clone(), amount: 100 },
+        &CollateralInput { token_address: strwa, amount: 100 },
+    );
+}
+
+#[test]
+fn test_register_borrow_asset_and_lp_deposit() {
+    let setup = setup_test();
 
-    assert_eq!(profile.token_yield_apr, 500); // 5% set in setup
-    assert_eq!(profile.rwa_token_address, setup.rwa_token_client.address);
+    // USDC is registered at initialization
+    let assets = setup.lending_pool_client.get_borrow_assets();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets.get(0).unwrap(), setup.usdc_client.address);
+
+    // Register a second stablecoin with its own rate model
+    let eurc_id = setup.env.register(UsdcMock, (&setup.admin, &10_000_000_000i128));
+    let eurc_client = UsdcMockClient::new(&setup.env, &eurc_id);
+    setup
+        .lending_pool_client
+        .register_borrow_asset(&setup.admin, &eurc_id, &900, &1500);
+
+    let config = setup.lending_pool_client.get_borrow_asset(&eurc_id).unwrap();
+    assert_eq!(config.interest_rate, 900);
+    assert_eq!(config.yield_share_percent, 1500);
+    assert_eq!(setup.lending_pool_client.get_borrow_assets().len(), 2);
+
+    // Liquidity is tracked separately per asset
+    eurc_client.transfer(&setup.admin, &setup.lp_user, &300_000);
+    setup
+        .lending_pool_client
+        .lp_deposit_asset(&setup.lp_user, &eurc_id, &300_000);
+
+    assert_eq!(setup.lending_pool_client.get_asset_total_liquidity(&eurc_id), 300_000);
+    assert_eq!(setup.lending_pool_client.get_total_liquidity(), 0);
+    assert_eq!(
+        setup
+            .lending_pool_client
+            .get_lp_deposit_asset(&setup.lp_user, &eurc_id)
+            .available_amount,
+        300_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Asset not borrowable")]
+fn test_lp_deposit_unregistered_asset() {
+    let setup = setup_test();
+
+    let other_id = setup.env.register(UsdcMock, (&setup.admin, &10_000_000_000i128));
+    setup
+        .lending_pool_client
+        .lp_deposit_asset(&setup.lp_user, &other_id, &1_000);
+}
+
+#[test]
+fn test_originate_loan_asset() {
+    let setup = setup_test();
+
+    let eurc_id = setup.env.register(UsdcMock, (&setup.admin, &10_000_000_000i128));
+    let eurc_client = UsdcMockClient::new(&setup.env, &eurc_id);
+    setup
+        .lending_pool_client
+        .register_borrow_asset(&setup.admin, &eurc_id, &900, &1500);
+    eurc_client.transfer(&setup.admin, &setup.lp_user, &1_000_000);
+    setup
+        .lending_pool_client
+        .lp_deposit_asset(&setup.lp_user, &eurc_id, &1_000_000);
+
+    let collateral = stake_collateral(&setup, 200_000);
+    setup.lending_pool_client.originate_loan_asset(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &eurc_id,
+        &100_000,
+        &12,
+    );
+
+    // The loan uses the asset's rate model and draws only on its liquidity
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert_eq!(loan.borrow_asset, eurc_id);
+    assert_eq!(loan.interest_rate, 900);
+    assert_eq!(eurc_client.balance(&setup.borrower), 100_000);
+    assert_eq!(
+        setup.lending_pool_client.get_asset_available_liquidity(&eurc_id),
+        900_000
+    );
+    assert_eq!(setup.lending_pool_client.get_available_liquidity(), 0);
+}
+
+#[test]
+fn test_migrate_usdc_liquidity() {
+    let setup = setup_test();
+    let usdc = setup.usdc_client.address.clone();
+
+    // Write liquidity under the keys used before multi-asset support
+    let legacy_deposit = LPDeposit {
+        depositor: setup.lp_user.clone(),
+        total_deposited: 500_000,
+        locked_amount: 100_000,
+        available_amount: 400_000,
+        total_interest_earned: 0,
+    };
+    setup.env.as_contract(&setup.lending_pool_client.address, || {
+        let storage = setup.env.storage().instance();
+        storage.set(&LegacyDataKey::LPDeposit(setup.lp_user.clone()), &legacy_deposit);
+        storage.set(&LegacyDataKey::TotalLiquidity, &500_000i128);
+        storage.set(&LegacyDataKey::TotalLockedLiquidity, &100_000i128);
+    });
+
+    setup
+        .lending_pool_client
+        .migrate_usdc_liquidity(&setup.admin, &vec![&setup.env, setup.lp_user.clone()]);
+
+    assert_eq!(setup.lending_pool_client.get_asset_total_liquidity(&usdc), 500_000);
+    assert_eq!(setup.lending_pool_client.get_available_liquidity(), 400_000);
+    assert_eq!(
+        setup.lending_pool_client.get_lp_deposit(&setup.lp_user),
+        legacy_deposit
+    );
+    setup.env.as_contract(&setup.lending_pool_client.address, || {
+        let storage = setup.env.storage().instance();
+        assert!(!storage.has(&LegacyDataKey::LPDeposit(setup.lp_user.clone())));
+        assert!(!storage.has(&LegacyDataKey::TotalLiquidity));
+        assert!(!storage.has(&LegacyDataKey::TotalLockedLiquidity));
+    });
+}
+
+#[test]
+fn test_migrate_usdc_loans() {
+    let setup = setup_test();
+    let collaterals = strwa_collateral(&setup, 1_000);
+
+    // A loan written by the version without borrow assets
+    let legacy = LegacyLoan {
+        borrower: setup.borrower.clone(),
+        collaterals: collaterals.clone(),
+        principal: 100_000,
+        outstanding_debt: 101_000,
+        interest_rate: 700,
+        start_time: 0,
+        end_time: 1_000,
+        last_interest_update: 500,
+        warnings_issued: 1,
+        last_warning_time: 400,
+        penalties: 50,
+        yield_share_percent: 1000,
+    };
+    setup.env.as_contract(&setup.lending_pool_client.address, || {
+        setup
+            .env
+            .storage()
+            .instance()
+            .set(&DataKey::Loan(setup.borrower.clone()), &legacy);
+    });
+
+    let borrowers = vec![&setup.env, setup.borrower.clone()];
+    setup
+        .lending_pool_client
+        .migrate_usdc_loans(&setup.admin, &borrowers);
+
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert_eq!(loan.borrow_asset, setup.usdc_client.address);
+    assert_eq!(loan.collaterals, collaterals);
+    assert_eq!(loan.outstanding_debt, 101_000);
+    assert_eq!(loan.warnings_issued, 1);
+    assert_eq!(loan.penalties, 50);
+
+    // Migrated loans are left alone on a second run
+    setup
+        .lending_pool_client
+        .migrate_usdc_loans(&setup.admin, &borrowers);
+    assert_eq!(setup.lending_pool_client.get_loan(&setup.borrower).unwrap(), loan);
+}
+
+#[test]
+fn test_register_vault_caches_decimals() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    setup
+        .lending_pool_client
+        .register_vault(&setup.admin, &strwa, &setup.vault_client.address);
+
+    let decimals = setup.lending_pool_client.get_asset_decimals(&strwa).unwrap();
+    assert_eq!(decimals.token_decimals, 18);
+    assert_eq!(decimals.price_decimals, 6);
+
+    // USDC decimals are cached at initialization
+    let usdc_decimals = setup
+        .lending_pool_client
+        .get_asset_decimals(&setup.usdc_client.address)
+        .unwrap();
+    assert_eq!(usdc_decimals.token_decimals, 7);
+}
+
+/// Register a 7-decimal borrow asset priced at 1.1 USDC and fund the pool with it
+fn register_priced_asset(setup: &TestSetup) -> Address {
+    let eurc_id = setup.env.register(UsdcMock, (&setup.admin, &10_000_000_000i128));
+    setup
+        .lending_pool_client
+        .register_borrow_asset(&setup.admin, &eurc_id, &900, &1500);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price(&eurc_id, &1_100_000);
+
+    UsdcMockClient::new(&setup.env, &eurc_id).transfer(&setup.admin, &setup.lp_user, &1_000_000);
+    setup
+        .lending_pool_client
+        .lp_deposit_asset(&setup.lp_user, &eurc_id, &1_000_000);
+    eurc_id
+}
+
+#[test]
+fn test_mixed_decimals_borrow_capacity() {
+    let setup = setup_test();
+    let eurc_id = register_priced_asset(&setup);
+
+    // 140_000 USDC of 18-decimal stRWA carries 100_000 USDC of debt at 140%,
+    // which is 90_909 of the 7-decimal asset at 1.1
+    let collateral = stake_collateral(&setup, 140_000);
+    setup.lending_pool_client.originate_loan_asset(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &eurc_id,
+        &90_909,
+        &12,
+    );
+
+    assert_eq!(
+        setup.lending_pool_client.get_loan(&setup.borrower).unwrap().principal,
+        90_909
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insufficient collateral for the required collateral ratio")]
+fn test_mixed_decimals_borrow_over_capacity() {
+    let setup = setup_test();
+    let eurc_id = register_priced_asset(&setup);
+
+    let collateral = stake_collateral(&setup, 140_000);
+    setup.lending_pool_client.originate_loan_asset(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &eurc_id,
+        &90_910,
+        &12,
+    );
+}
+
+#[test]
+fn test_oracle_config_with_secondary() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    // Defaults to the pool oracle alone
+    let config = setup.lending_pool_client.get_oracle_config(&strwa);
+    assert_eq!(config.primary, setup.oracle_client);
+    assert_eq!(config.secondary, None);
+
+    let secondary = setup.env.register(MockOracle, ());
+    setup.lending_pool_client.set_oracle_config(
+        &setup.admin,
+        &strwa,
+        &setup.oracle_client,
+        &Some(secondary.clone()),
+        &200,
+    );
+
+    let config = setup.lending_pool_client.get_oracle_config(&strwa);
+    assert_eq!(config.secondary, Some(secondary));
+    assert_eq!(config.secondary_decimals, 6);
+    assert_eq!(config.max_deviation_bps, 200);
+}
+
+/// Register a second MockOracle as the stRWA secondary feed
+fn add_secondary_oracle<'a>(
+    setup: &'a TestSetup,
+    max_deviation_bps: i128,
+) -> MockOracleClient<'a> {
+    let secondary = setup.env.register(MockOracle, ());
+    setup.lending_pool_client.set_oracle_config(
+        &setup.admin,
+        &setup.strwa_token_client.address,
+        &setup.oracle_client,
+        &Some(secondary.clone()),
+        &max_deviation_bps,
+    );
+    MockOracleClient::new(&setup.env, &secondary)
+}
+
+#[test]
+fn test_oracles_within_deviation_use_primary() {
+    let setup = setup_test();
+    let secondary = add_secondary_oracle(&setup, 200);
+
+    // 1% apart is within the 2% tolerance
+    secondary.set_price(&setup.strwa_token_client.address, &1_010_000);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 140_000);
+
+    // Capacity at the primary's 1.0 price is exactly 100_000
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+    assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Oracle price is stale")]
+fn test_oracle_deviation_rejects_price() {
+    let setup = setup_test();
+    let secondary = add_secondary_oracle(&setup, 200);
+
+    // 10% apart exceeds the 2% tolerance
+    secondary.set_price(&setup.strwa_token_client.address, &1_100_000);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+}
+
+#[test]
+fn test_stale_primary_falls_back_to_secondary() {
+    let setup = setup_test();
+    let secondary = add_secondary_oracle(&setup, 200);
+    secondary.set_price(&setup.strwa_token_client.address, &700_000);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    // The primary stops reporting
+    setup.env.ledger().with_mut(|l| l.timestamp = 100_000);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price_timestamp(&0);
+
+    // Valued at the secondary's 0.7: 140_000 of collateral carries 100_000 at 140%
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+    assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Insufficient collateral for the required collateral ratio")]
+fn test_fallback_price_limits_borrowing() {
+    let setup = setup_test();
+    let secondary = add_secondary_oracle(&setup, 200);
+    secondary.set_price(&setup.strwa_token_client.address, &700_000);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.env.ledger().with_mut(|l| l.timestamp = 100_000);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price_timestamp(&0);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_001,
+        &12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Oracle price is stale")]
+fn test_price_older_than_max_age_is_stale() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    // No override: the oracle's feed config, or one day without one, applies
+    assert_eq!(setup.lending_pool_client.get_oracle_config(&strwa).max_age, None);
+
+    setup
+        .lending_pool_client
+        .set_price_max_age(&setup.admin, &strwa, &3600);
+    assert_eq!(
+        setup.lending_pool_client.get_oracle_config(&strwa).max_age,
+        Some(3600)
+    );
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.env.ledger().with_mut(|l| l.timestamp = 10_000);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price_timestamp(&6_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Oracle price is stale")]
+fn test_oracle_max_age_read_at_check_time() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    setup.lending_pool_client.set_oracle_config(
+        &setup.admin,
+        &strwa,
+        &setup.oracle_client,
+        &None,
+        &200,
+    );
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    // A 4_000 second old price is fine under the one day default...
+    setup.env.ledger().with_mut(|l| l.timestamp = 10_000);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price_timestamp(&6_000);
+
+    // ...until the oracle tightens the feed after the pool was configured
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_max_age(&strwa, &3600);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+}
+
+#[test]
+fn test_oracle_failure_mode() {
+    let setup = setup_test();
+
+    assert_eq!(
+        setup.lending_pool_client.get_oracle_failure_mode(),
+        OracleFailureMode::RepayOnly
+    );
+
+    setup
+        .lending_pool_client
+        .set_oracle_failure_mode(&setup.admin, &OracleFailureMode::Frozen);
+    assert_eq!(
+        setup.lending_pool_client.get_oracle_failure_mode(),
+        OracleFailureMode::Frozen
+    );
+}
+
+/// Open a 100_000 USDC loan, then let every oracle price go stale
+fn loan_with_stale_prices(setup: &TestSetup) {
+    provide_liquidity(setup, 1_000_000);
+    let collateral = stake_collateral(setup, 200_000);
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(setup, collateral),
+        &100_000,
+        &12,
+    );
+
+    setup.env.ledger().with_mut(|l| l.timestamp = 100_000);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price_timestamp(&0);
+}
+
+#[test]
+fn test_repay_only_mode_allows_repayment_without_prices() {
+    let setup = setup_test();
+    loan_with_stale_prices(&setup);
+
+    let outstanding = setup
+        .lending_pool_client
+        .get_loan(&setup.borrower)
+        .unwrap()
+        .outstanding_debt;
+    setup.lending_pool_client.repay_loan(&setup.borrower, &10_000);
+
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert!(loan.outstanding_debt < outstanding);
+}
+
+#[test]
+#[should_panic(expected = "Oracle unavailable: loan actions frozen")]
+fn test_frozen_mode_blocks_repayment_without_prices() {
+    let setup = setup_test();
+    loan_with_stale_prices(&setup);
+
+    setup
+        .lending_pool_client
+        .set_oracle_failure_mode(&setup.admin, &OracleFailureMode::Frozen);
+    setup.lending_pool_client.repay_loan(&setup.borrower, &10_000);
+}
+
+#[test]
+#[should_panic(expected = "Collateral supply exceeds attested reserve")]
+fn test_originate_loan_requires_attested_reserve() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    setup
+        .lending_pool_client
+        .register_vault(&setup.admin, &strwa, &setup.vault_client.address);
+
+    // Attested reserve is below the RWA token's on-chain supply
+    MockOracleClient::new(&setup.env, &setup.oracle_client)
+        .set_reserve(&setup.rwa_token_client.address, &1_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &vec![
+            &setup.env,
+            CollateralInput {
+                token_address: strwa,
+                amount: 200_000,
+            },
+        ],
+        &100_000,
+        &12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Reserve attestation is stale")]
+fn test_originate_loan_rejects_stale_attestation() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup
+        .lending_pool_client
+        .set_attestation_max_age(&setup.admin, &(7 * 24 * 60 * 60));
+
+    setup.env.ledger().with_mut(|l| l.timestamp = 10 * 24 * 60 * 60);
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_attestation_timestamp(&0);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Collateral supply exceeds attested reserve")]
+fn test_adjust_collateral_requires_attested_reserve() {
+    let setup = setup_test();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 400_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral / 2),
+        &100_000,
+        &12,
+    );
+
+    // Reserve falls short after the loan: no more collateral may be added
+    MockOracleClient::new(&setup.env, &setup.oracle_client)
+        .set_reserve(&setup.rwa_token_client.address, &1_000);
+
+    setup.lending_pool_client.adjust_collateral(
+        &setup.borrower,
+        &vec![
+            &setup.env,
+            CollateralChange {
+                action: Action::Add,
+                token_address: setup.strwa_token_client.address.clone(),
+                amount: collateral / 2,
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_originate_loan_uses_collateral_ratio() {
+    let setup = setup_test();
+    let strwa = setup.strwa_token_client.address.clone();
+
+    // At 120% a 130k collateral supports a 100k loan; the 140% default would not
+    setup
+        .lending_pool_client
+        .set_collateral_ratio(&setup.admin, &strwa, &12000);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 130_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+    assert!(setup.lending_pool_client.get_loan(&setup.borrower).is_some());
+}
+
+/// Second asset class over the same RWA, registered with the pool
+fn deploy_second_vault<'a>(setup: &TestSetup<'a>) -> (StRwaTokenClient<'a>, RwaVaultClient<'a>) {
+    let strwa_b_id = setup.env.register(StRwaToken, ());
+    let strwa_b = StRwaTokenClient::new(&setup.env, &strwa_b_id);
+    strwa_b.initialize(&setup.admin);
+    let vault_b_id = setup.env.register(RwaVault, ());
+    let vault_b = RwaVaultClient::new(&setup.env, &vault_b_id);
+    vault_b.initialize(&setup.admin, &setup.rwa_token_client.address, &strwa_b_id);
+    vault_b.set_usdc_address(&setup.usdc_client.address);
+    vault_b.set_lending_pool(&setup.lending_pool_client.address);
+    strwa_b.set_vault_address(&vault_b_id);
+    setup.rwa_token_client.allow_user(&vault_b_id, &setup.admin);
+    setup
+        .lending_pool_client
+        .register_vault(&setup.admin, &strwa_b_id, &vault_b_id);
+    (strwa_b, vault_b)
+}
+
+/// Borrower stakes `amount` RWA into the second vault
+fn stake_in_vault(setup: &TestSetup, vault: &RwaVaultClient, amount: i128) {
+    setup
+        .rwa_token_client
+        .approve(&setup.borrower, &vault.address, &amount, &100);
+    vault.stake(&setup.borrower, &amount);
+}
+
+#[test]
+fn test_swap_collateral_moves_borrower_markers() {
+    let setup = setup_test();
+    let (strwa_b, vault_b) = deploy_second_vault(&setup);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+    stake_in_vault(&setup, &vault_b, collateral);
+
+    setup.lending_pool_client.swap_collateral(
+        &setup.borrower,
+        &CollateralInput {
+            token_address: setup.strwa_token_client.address.clone(),
+            amount: collateral,
+        },
+        &CollateralInput {
+            token_address: strwa_b.address.clone(),
+            amount: collateral,
+        },
+    );
+
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    assert_eq!(loan.collaterals.len(), 1);
+    assert_eq!(loan.collaterals.get(0).unwrap().token_address, strwa_b.address);
+
+    // The new vault locks the stake for the loan
+    let new_stake = vault_b.get_stake_info(&setup.borrower).unwrap();
+    assert!(new_stake.is_borrower);
+    assert_eq!(new_stake.borrowed_amount, loan.principal);
+
+    // The vault no longer backing the loan releases the borrower
+    let old_stake = setup.vault_client.get_stake_info(&setup.borrower).unwrap();
+    assert!(!old_stake.is_borrower);
+    assert_eq!(old_stake.borrowed_amount, 0);
+
+    assert_eq!(setup.strwa_token_client.balance(&setup.borrower), collateral);
+    assert_eq!(strwa_b.balance(&setup.lending_pool_client.address), collateral);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient collateral after swap")]
+fn test_swap_collateral_rejects_undercollateralized_loan() {
+    let setup = setup_test();
+    let (strwa_b, vault_b) = deploy_second_vault(&setup);
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+    stake_in_vault(&setup, &vault_b, collateral);
+
+    // stRWA-B at 0.5 leaves 100_000 of collateral against 100_000 of debt
+    MockOracleClient::new(&setup.env, &setup.oracle_client).set_price(&strwa_b.address, &500_000);
+
+    setup.lending_pool_client.swap_collateral(
+        &setup.borrower,
+        &CollateralInput {
+            token_address: setup.strwa_token_client.address.clone(),
+            amount: collateral,
+        },
+        &CollateralInput {
+            token_address: strwa_b.address.clone(),
+            amount: collateral,
+        },
+    );
+}
+
+#[test]
+fn test_swap_into_vault_without_stake() {
+    let setup = setup_test();
+    let (strwa_b, vault_b) = deploy_second_vault(&setup);
+    let strwa_b_id = strwa_b.address.clone();
+
+    provide_liquidity(&setup, 1_000_000);
+    let collateral = stake_collateral(&setup, 200_000);
+
+    setup.lending_pool_client.originate_loan(
+        &setup.borrower,
+        &strwa_collateral(&setup, collateral),
+        &100_000,
+        &12,
+    );
+
+    // The borrower buys stRWA-B from a staker instead of staking it
+    let staker = Address::generate(&setup.env);
+    setup.rwa_token_client.allow_user(&staker, &setup.admin);
+    setup
+        .rwa_token_client
+        .transfer(&setup.admin, &staker, &collateral);
+    vault_b.stake(&staker, &collateral);
+    strwa_b.transfer(&staker, &setup.borrower, &collateral);
+
+    let ledger_time = 30 * 24 * 60 * 60;
+    setup.env.ledger().with_mut(|l| l.timestamp = ledger_time);
+    setup.lending_pool_client.swap_collateral(
+        &setup.borrower,
+        &CollateralInput {
+            token_address: setup.strwa_token_client.address.clone(),
+            amount: collateral,
+        },
+        &CollateralInput {
+            token_address: strwa_b_id.clone(),
+            amount: collateral,
+        },
+    );
+
+    let loan = setup.lending_pool_client.get_loan(&setup.borrower).unwrap();
+    let stake = vault_b.get_stake_info(&setup.borrower).unwrap();
+    assert!(stake.is_borrower);
+    assert_eq!(stake.loan_period, loan.end_time - ledger_time);
+    assert_eq!(strwa_b.balance(&setup.lending_pool_client.address), collateral);
 }
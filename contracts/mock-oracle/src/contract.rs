@@ -1,23 +1,125 @@
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map, contracttype};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Map,
+    Symbol, Vec,
+};
 
-/// Price data structure
+/// SEP-40 asset identifier
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Price data structure (SEP-40 compatible)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PriceData {
-    pub price: i128,       // Price in basis points (e.g., 10500 = 105.00 USDC)
+    pub price: i128,       // Price with PRICE_DECIMALS decimals (e.g., 1_050_000 = 1.05 USDC)
     pub timestamp: u64,    // Unix timestamp of last update
 }
 
+/// Per-asset limits on how far a single update may move the price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviationGuard {
+    pub max_update_bps: i128,  // Max move vs. the latest price
+    pub max_window_bps: i128,  // Max move vs. the price `window_seconds` ago
+    pub window_seconds: u64,
+}
+
+/// A supported asset in the oracle's registry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetInfo {
+    pub asset: Address,
+    pub symbol: Symbol,
+    pub decimals: u32,   // Decimals of the asset's token
+}
+
+/// Proof-of-reserve / NAV report for an RWA token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub nav: i128,              // Reported NAV per token, PRICE_DECIMALS decimals
+    pub reserve_amount: i128,   // Off-chain reserve backing the token, in token units
+    pub custodian_id: Symbol,
+    pub report_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// Per-asset staleness limit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedConfig {
+    pub max_age: u64,    // Seconds after which the latest price is stale
+}
+
+/// A price update held back by the deviation guard, awaiting confirmation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPrice {
+    pub price_data: PriceData,
+    pub reporter: Address,
+}
+
+/// A single reporter's price within a round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub reporter: Address,
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Submissions collected for an asset until the quorum is reached
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub started_at: u64,
+    pub submissions: Vec<Submission>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuorumConfig {
+    pub quorum: u32,
+    pub window_seconds: u64,
+}
+
+/// Default seconds after which a price is considered stale
+const DEFAULT_MAX_AGE: u64 = 86400;
+
+/// Number of decimals in submitted prices (matches the oracle price bot)
+const PRICE_DECIMALS: u32 = 6;
+
+/// Expected seconds between price updates (matches the oracle price bot schedule)
+const RESOLUTION: u32 = 60;
+
+/// Number of observations kept per asset in the price history ring buffer
+const HISTORY_SIZE: u32 = 48;
+
+/// Price history TTL in ledgers (about 30 days at 5 seconds per ledger),
+/// topped up on every write once fewer than about 7 days remain
+const HISTORY_TTL_EXTEND: u32 = 518_400;
+const HISTORY_TTL_THRESHOLD: u32 = 120_960;
+
 #[contract]
 pub struct MockOracle;
 
 #[contractimpl]
 impl MockOracle {
     /// Constructor
-    /// Sets the authorized bot address that can submit prices
-    pub fn __constructor(e: &Env, bot_address: Address) {
-        // Store the bot address
+    /// Sets the admin and the authorized bot address that can submit prices
+    pub fn __constructor(e: &Env, admin: Address, bot_address: Address) {
+        // Store the admin and bot addresses
+        e.storage().instance().set(&symbol_short!("admin"), &admin);
         e.storage().instance().set(&symbol_short!("bot"), &bot_address);
+
+        // The bot is the first registered reporter
+        let mut reporters: Vec<Address> = Vec::new(e);
+        reporters.push_back(bot_address);
+        e.storage().instance().set(&symbol_short!("reporters"), &reporters);
         
         // Initialize empty price map
         let prices: Map<Address, PriceData> = Map::new(e);
@@ -26,16 +128,11 @@ impl MockOracle {
     
     /// Submit a new price for an asset (bot-only)
     pub fn submit_price(e: &Env, bot: Address, asset: Address, price: i128) {
-        // Verify caller is the authorized bot
-        let authorized_bot: Address = e.storage().instance()
-            .get(&symbol_short!("bot"))
-            .expect("Bot address not set");
-
         // Require authentication from the bot
         bot.require_auth();
 
-        // Verify the caller is the authorized bot
-        if bot != authorized_bot {
+        // Verify the caller is a registered reporter
+        if !Self::is_reporter(e, &bot) {
             panic!("Unauthorized: only bot can submit prices");
         }
 
@@ -53,36 +150,16 @@ impl MockOracle {
             timestamp,
         };
 
-        // Load prices map
-        let mut prices: Map<Address, PriceData> = e.storage().instance()
-            .get(&symbol_short!("prices"))
-            .unwrap_or(Map::new(e));
-
-        // Update price for asset
-        prices.set(asset.clone(), price_data);
-
-        // Save updated prices map
-        e.storage().instance().set(&symbol_short!("prices"), &prices);
-
-        // Emit event (for off-chain monitoring)
-        e.events().publish(
-            (symbol_short!("price_upd"), asset),
-            (price, timestamp)
-        );
+        Self::record_submission(e, &bot, &asset, price_data);
     }
 
     /// Alias for submit_price (for bot compatibility)
     pub fn set_price(e: &Env, asset_address: Address, price: i128, timestamp: u64, source: Address) {
-        // Verify caller is the authorized bot
-        let authorized_bot: Address = e.storage().instance()
-            .get(&symbol_short!("bot"))
-            .expect("Bot address not set");
-
         // Require authentication from the source (bot)
         source.require_auth();
 
-        // Verify the caller is the authorized bot
-        if source != authorized_bot {
+        // Verify the caller is a registered reporter
+        if !Self::is_reporter(e, &source) {
             panic!("Unauthorized: only bot can set prices");
         }
 
@@ -91,39 +168,841 @@ impl MockOracle {
             panic!("Price must be positive");
         }
 
+        if timestamp > e.ledger().timestamp() {
+            panic!("Invalid price timestamp");
+        }
+
         // Create price data (use provided timestamp or current ledger time)
         let price_data = PriceData {
             price,
             timestamp: if timestamp > 0 { timestamp } else { e.ledger().timestamp() },
         };
 
+        Self::record_submission(e, &source, &asset_address, price_data);
+    }
+
+    /// Submit prices for several assets in one call. Each update is
+    /// (asset, price, timestamp); a zero timestamp uses the ledger time.
+    pub fn set_prices(e: &Env, source: Address, updates: Vec<(Address, i128, u64)>) {
+        source.require_auth();
+
+        if !Self::is_reporter(e, &source) {
+            panic!("Unauthorized: only bot can set prices");
+        }
+
+        if updates.is_empty() {
+            panic!("No price updates provided");
+        }
+
+        for (asset, price, timestamp) in updates.iter() {
+            if price <= 0 {
+                panic!("Price must be positive");
+            }
+            if timestamp > e.ledger().timestamp() {
+                panic!("Invalid price timestamp");
+            }
+
+            let price_data = PriceData {
+                price,
+                timestamp: if timestamp > 0 { timestamp } else { e.ledger().timestamp() },
+            };
+
+            Self::record_submission(e, &source, &asset, price_data);
+        }
+    }
+
+    /// Submit a price signed off-chain by a registered reporter key. Anyone may
+    /// relay the payload; the ed25519 signature must cover the XDR encoding of
+    /// (oracle, asset, price, timestamp, nonce), where `oracle` is this contract's
+    /// address, and nonces must strictly increase per key.
+    pub fn submit_signed_price(
+        e: &Env,
+        asset: Address,
+        price: i128,
+        timestamp: u64,
+        nonce: u64,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) {
+        let signers: Map<BytesN<32>, Address> = e.storage().instance()
+            .get(&symbol_short!("signers"))
+            .unwrap_or(Map::new(e));
+        let reporter = signers.get(public_key.clone()).expect("Unknown signer key");
+
+        if !Self::is_reporter(e, &reporter) {
+            panic!("Unauthorized: signer is not a reporter");
+        }
+
+        let mut nonces: Map<BytesN<32>, u64> = e.storage().instance()
+            .get(&symbol_short!("nonces"))
+            .unwrap_or(Map::new(e));
+        if let Some(last_nonce) = nonces.get(public_key.clone()) {
+            if nonce <= last_nonce {
+                panic!("Nonce already used");
+            }
+        }
+
+        if price <= 0 {
+            panic!("Price must be positive");
+        }
+        if timestamp == 0 || timestamp > e.ledger().timestamp() {
+            panic!("Invalid price timestamp");
+        }
+
+        let message = (e.current_contract_address(), asset.clone(), price, timestamp, nonce).to_xdr(e);
+        e.crypto().ed25519_verify(&public_key, &message, &signature);
+
+        nonces.set(public_key, nonce);
+        e.storage().instance().set(&symbol_short!("nonces"), &nonces);
+
+        Self::record_submission(e, &reporter, &asset, PriceData { price, timestamp });
+    }
+
+    /// Add a reporter's submission to the asset's current round and publish
+    /// the median once the quorum is reached within the round window
+    fn record_submission(e: &Env, reporter: &Address, asset: &Address, price_data: PriceData) {
+        let config = Self::get_quorum_config(e);
+
+        let mut rounds: Map<Address, Round> = e.storage().instance()
+            .get(&symbol_short!("rounds"))
+            .unwrap_or(Map::new(e));
+
+        let now = e.ledger().timestamp();
+        let mut round = match rounds.get(asset.clone()) {
+            Some(round) if now.saturating_sub(round.started_at) <= config.window_seconds => round,
+            _ => Round {
+                started_at: now,
+                submissions: Vec::new(e),
+            },
+        };
+
+        if round.submissions.iter().any(|s| s.reporter == *reporter) {
+            panic!("Reporter already submitted this round");
+        }
+
+        round.submissions.push_back(Submission {
+            reporter: reporter.clone(),
+            price: price_data.price,
+            timestamp: price_data.timestamp,
+        });
+
+        if round.submissions.len() < config.quorum {
+            rounds.set(asset.clone(), round);
+            e.storage().instance().set(&symbol_short!("rounds"), &rounds);
+            return;
+        }
+
+        rounds.remove(asset.clone());
+        e.storage().instance().set(&symbol_short!("rounds"), &rounds);
+
+        // The round is only as fresh as its oldest submission
+        let mut reporters = Vec::new(e);
+        let mut timestamp = u64::MAX;
+        for submission in round.submissions.iter() {
+            reporters.push_back(submission.reporter);
+            timestamp = timestamp.min(submission.timestamp);
+        }
+        let median = Self::median(e, &round.submissions);
+
+        let mut contributors: Map<Address, Vec<Address>> = e.storage().instance()
+            .get(&symbol_short!("contribs"))
+            .unwrap_or(Map::new(e));
+        contributors.set(asset.clone(), reporters.clone());
+        e.storage().instance().set(&symbol_short!("contribs"), &contributors);
+
+        e.events().publish((symbol_short!("round"), asset.clone()), (median, reporters));
+
+        Self::accept_price(e, reporter, asset, PriceData {
+            price: median,
+            timestamp,
+        });
+    }
+
+    /// Middle submitted price. Quorums are odd, so no single reporter can
+    /// move the result by averaging with it.
+    fn median(e: &Env, submissions: &Vec<Submission>) -> i128 {
+        // Insertion sort; rounds are small
+        let mut sorted: Vec<i128> = Vec::new(e);
+        for submission in submissions.iter() {
+            let index = sorted
+                .iter()
+                .position(|price| price > submission.price)
+                .unwrap_or(sorted.len() as usize);
+            sorted.insert(index as u32, submission.price);
+        }
+
+        sorted.get(sorted.len() / 2).unwrap()
+    }
+
+    /// Store a reported price, or hold it as pending if it trips the deviation guard
+    fn accept_price(e: &Env, reporter: &Address, asset: &Address, price_data: PriceData) {
+        if Self::exceeds_guard(e, asset, &price_data) {
+            let mut pending: Map<Address, PendingPrice> = e.storage().instance()
+                .get(&symbol_short!("pending"))
+                .unwrap_or(Map::new(e));
+            pending.set(asset.clone(), PendingPrice {
+                price_data: price_data.clone(),
+                reporter: reporter.clone(),
+            });
+            e.storage().instance().set(&symbol_short!("pending"), &pending);
+
+            e.events().publish(
+                (symbol_short!("price_hld"), asset.clone()),
+                (price_data.price, reporter.clone())
+            );
+            return;
+        }
+
+        Self::store_price(e, asset, price_data);
+    }
+
+    /// Record a new price as the latest value and in the asset's history.
+    /// Observations older than the latest price are ignored, so the latest
+    /// price never moves backwards and the history stays sorted.
+    fn store_price(e: &Env, asset: &Address, price_data: PriceData) {
         // Load prices map
         let mut prices: Map<Address, PriceData> = e.storage().instance()
             .get(&symbol_short!("prices"))
             .unwrap_or(Map::new(e));
 
+        if let Some(latest) = prices.get(asset.clone()) {
+            if price_data.timestamp < latest.timestamp {
+                return;
+            }
+        }
+
         // Update price for asset
-        prices.set(asset_address.clone(), price_data);
+        prices.set(asset.clone(), price_data.clone());
 
         // Save updated prices map
         e.storage().instance().set(&symbol_short!("prices"), &prices);
 
+        // Append to the ring buffer, dropping the oldest observation when full
+        let mut history = Self::load_history(e, asset);
+        match history.last() {
+            Some(last) if price_data.timestamp == last.timestamp => {
+                history.set(history.len() - 1, price_data.clone());
+            }
+            _ => {
+                if history.len() >= HISTORY_SIZE {
+                    history.pop_front();
+                }
+                history.push_back(price_data.clone());
+            }
+        }
+        let history_key = (symbol_short!("history"), asset.clone());
+        e.storage().persistent().set(&history_key, &history);
+        e.storage()
+            .persistent()
+            .extend_ttl(&history_key, HISTORY_TTL_THRESHOLD, HISTORY_TTL_EXTEND);
+
         // Emit event (for off-chain monitoring)
         e.events().publish(
-            (symbol_short!("price_upd"), asset_address),
-            (price, timestamp)
+            (symbol_short!("price_upd"), asset.clone()),
+            (price_data.price, price_data.timestamp)
         );
     }
+
+    /// Price history for an asset, kept in persistent storage so it does not
+    /// grow the instance entry loaded on every call
+    fn load_history(e: &Env, asset: &Address) -> Vec<PriceData> {
+        e.storage()
+            .persistent()
+            .get(&(symbol_short!("history"), asset.clone()))
+            .unwrap_or(Vec::new(e))
+    }
+
+    // ========================================================================
+    // Deviation Guard & Circuit Breaker
+    // ========================================================================
+
+    /// Set the maximum price move per update and per time window for an asset (admin-only)
+    pub fn set_deviation_guard(
+        e: &Env,
+        asset: Address,
+        max_update_bps: i128,
+        max_window_bps: i128,
+        window_seconds: u64,
+    ) {
+        Self::require_admin(e);
+
+        if max_update_bps <= 0 || max_window_bps <= 0 {
+            panic!("Deviation limits must be positive");
+        }
+
+        let mut guards: Map<Address, DeviationGuard> = e.storage().instance()
+            .get(&symbol_short!("guards"))
+            .unwrap_or(Map::new(e));
+        guards.set(asset, DeviationGuard {
+            max_update_bps,
+            max_window_bps,
+            window_seconds,
+        });
+        e.storage().instance().set(&symbol_short!("guards"), &guards);
+    }
+
+    /// Get the deviation guard for an asset, if any
+    pub fn get_deviation_guard(e: &Env, asset: Address) -> Option<DeviationGuard> {
+        let guards: Map<Address, DeviationGuard> = e.storage().instance()
+            .get(&symbol_short!("guards"))
+            .unwrap_or(Map::new(e));
+        guards.get(asset)
+    }
+
+    // ========================================================================
+    // Asset Registry
+    // ========================================================================
+
+    /// Register a supported asset with its display symbol and token decimals (admin-only)
+    pub fn register_asset(e: &Env, asset: Address, symbol: Symbol, decimals: u32) {
+        Self::require_admin(e);
+
+        let mut registry: Map<Address, AssetInfo> = e.storage().instance()
+            .get(&symbol_short!("registry"))
+            .unwrap_or(Map::new(e));
+        registry.set(asset.clone(), AssetInfo {
+            asset: asset.clone(),
+            symbol: symbol.clone(),
+            decimals,
+        });
+        e.storage().instance().set(&symbol_short!("registry"), &registry);
+
+        e.events().publish((symbol_short!("asset_reg"), asset), (symbol, decimals));
+    }
+
+    /// Remove an asset from the registry (admin-only). Its price history is kept.
+    pub fn unregister_asset(e: &Env, asset: Address) {
+        Self::require_admin(e);
+
+        let mut registry: Map<Address, AssetInfo> = e.storage().instance()
+            .get(&symbol_short!("registry"))
+            .unwrap_or(Map::new(e));
+        if registry.get(asset.clone()).is_none() {
+            panic!("Asset not registered");
+        }
+        registry.remove(asset.clone());
+        e.storage().instance().set(&symbol_short!("registry"), &registry);
+
+        e.events().publish((symbol_short!("asset_rem"), asset), ());
+    }
+
+    /// Registry entry for an asset, if registered
+    pub fn get_asset_info(e: &Env, asset: Address) -> Option<AssetInfo> {
+        let registry: Map<Address, AssetInfo> = e.storage().instance()
+            .get(&symbol_short!("registry"))
+            .unwrap_or(Map::new(e));
+        registry.get(asset)
+    }
+
+    /// All registered assets, for the bot and frontend to discover feeds
+    pub fn list_assets(e: &Env) -> Vec<AssetInfo> {
+        let registry: Map<Address, AssetInfo> = e.storage().instance()
+            .get(&symbol_short!("registry"))
+            .unwrap_or(Map::new(e));
+        registry.values()
+    }
+
+    // ========================================================================
+    // Proof of Reserve
+    // ========================================================================
+
+    /// Record a custodian's reserve / NAV attestation for an RWA token (reporters only)
+    pub fn submit_attestation(
+        e: &Env,
+        reporter: Address,
+        rwa_token: Address,
+        nav: i128,
+        reserve_amount: i128,
+        custodian_id: Symbol,
+        report_hash: BytesN<32>,
+        timestamp: u64,
+    ) {
+        reporter.require_auth();
+
+        if !Self::is_reporter(e, &reporter) {
+            panic!("Unauthorized: only reporters can attest reserves");
+        }
+        if nav <= 0 || reserve_amount < 0 {
+            panic!("Invalid attestation amounts");
+        }
+        if timestamp > e.ledger().timestamp() {
+            panic!("Invalid attestation timestamp");
+        }
+
+        let mut attestations: Map<Address, Attestation> = e.storage().instance()
+            .get(&symbol_short!("attests"))
+            .unwrap_or(Map::new(e));
+
+        if let Some(previous) = attestations.get(rwa_token.clone()) {
+            if timestamp <= previous.timestamp {
+                panic!("Attestation older than current report");
+            }
+        }
+
+        attestations.set(rwa_token.clone(), Attestation {
+            nav,
+            reserve_amount,
+            custodian_id: custodian_id.clone(),
+            report_hash,
+            timestamp,
+        });
+        e.storage().instance().set(&symbol_short!("attests"), &attestations);
+
+        e.events().publish(
+            (symbol_short!("attest"), rwa_token),
+            (nav, reserve_amount, custodian_id),
+        );
+    }
+
+    /// Latest reserve attestation for an RWA token, if any
+    pub fn get_attestation(e: &Env, rwa_token: Address) -> Option<Attestation> {
+        let attestations: Map<Address, Attestation> = e.storage().instance()
+            .get(&symbol_short!("attests"))
+            .unwrap_or(Map::new(e));
+        attestations.get(rwa_token)
+    }
+
+    // ========================================================================
+    // Staleness
+    // ========================================================================
+
+    /// Set the maximum price age for an asset (admin-only)
+    pub fn set_feed_config(e: &Env, asset: Address, max_age: u64) {
+        Self::require_admin(e);
+
+        if max_age == 0 {
+            panic!("Max age must be positive");
+        }
+
+        let mut configs: Map<Address, FeedConfig> = e.storage().instance()
+            .get(&symbol_short!("feeds"))
+            .unwrap_or(Map::new(e));
+        configs.set(asset.clone(), FeedConfig { max_age });
+        e.storage().instance().set(&symbol_short!("feeds"), &configs);
+
+        e.events().publish((symbol_short!("feed_cfg"), asset), max_age);
+    }
+
+    /// Max age for an asset (defaults to one day)
+    pub fn get_feed_config(e: &Env, asset: Address) -> FeedConfig {
+        let configs: Map<Address, FeedConfig> = e.storage().instance()
+            .get(&symbol_short!("feeds"))
+            .unwrap_or(Map::new(e));
+        configs.get(asset).unwrap_or(FeedConfig {
+            max_age: DEFAULT_MAX_AGE,
+        })
+    }
+
+    /// Whether the asset has no price, or its latest price is older than its max age.
+    /// A derived feed is stale when either of its component feeds is.
+    pub fn is_stale(e: &Env, asset: Address) -> bool {
+        match Self::get_derived_feed(e, asset.clone()) {
+            Some(quote) => Self::feed_is_stale(e, &asset) || Self::feed_is_stale(e, &quote),
+            None => Self::feed_is_stale(e, &asset),
+        }
+    }
+
+    fn feed_is_stale(e: &Env, asset: &Address) -> bool {
+        match Self::load_price(e, asset) {
+            Some(price_data) => {
+                let max_age = Self::get_feed_config(e, asset.clone()).max_age;
+                e.ledger().timestamp().saturating_sub(price_data.timestamp) > max_age
+            }
+            None => true,
+        }
+    }
+
+    /// Latest price for an asset, panicking if it is missing, stale or frozen
+    pub fn get_price_checked(e: &Env, asset: Address) -> PriceData {
+        if Self::is_frozen(e, asset.clone()) {
+            panic!("Price feed frozen");
+        }
+        if Self::is_stale(e, asset.clone()) {
+            panic!("Price is stale");
+        }
+        Self::current_price(e, &asset).expect("Price feed frozen")
+    }
+
+    // ========================================================================
+    // Derived Feeds
+    // ========================================================================
+
+    /// Price `asset` through a quote feed: its submitted prices are in the quote
+    /// currency (e.g. EUR or XLM) and are multiplied by the quote/USD price on read (admin-only)
+    pub fn set_derived_feed(e: &Env, asset: Address, quote: Address) {
+        Self::require_admin(e);
+
+        if asset == quote {
+            panic!("Asset cannot be quoted in itself");
+        }
+        if Self::get_derived_feed(e, quote.clone()).is_some() {
+            panic!("Quote feed cannot be derived");
+        }
+
+        let mut derived: Map<Address, Address> = e.storage().instance()
+            .get(&symbol_short!("derived"))
+            .unwrap_or(Map::new(e));
+        if derived.values().contains(&asset) {
+            panic!("Asset is the quote of a derived feed");
+        }
+        derived.set(asset.clone(), quote.clone());
+        e.storage().instance().set(&symbol_short!("derived"), &derived);
+
+        e.events().publish((symbol_short!("derived"), asset), quote);
+    }
+
+    /// Stop deriving an asset's price; its submitted prices are read as USD again (admin-only)
+    pub fn remove_derived_feed(e: &Env, asset: Address) {
+        Self::require_admin(e);
+
+        let mut derived: Map<Address, Address> = e.storage().instance()
+            .get(&symbol_short!("derived"))
+            .unwrap_or(Map::new(e));
+        if derived.get(asset.clone()).is_none() {
+            panic!("Asset is not a derived feed");
+        }
+        derived.remove(asset);
+        e.storage().instance().set(&symbol_short!("derived"), &derived);
+    }
+
+    /// Quote asset a derived feed is priced through, if any
+    pub fn get_derived_feed(e: &Env, asset: Address) -> Option<Address> {
+        let derived: Map<Address, Address> = e.storage().instance()
+            .get(&symbol_short!("derived"))
+            .unwrap_or(Map::new(e));
+        derived.get(asset)
+    }
+
+    /// USD price for an asset, computing derived feeds from their components.
+    /// A derived price carries the older of the two component timestamps.
+    fn current_price(e: &Env, asset: &Address) -> Option<PriceData> {
+        let price_data = Self::load_price(e, asset)?;
+        let quote = match Self::get_derived_feed(e, asset.clone()) {
+            Some(quote) => quote,
+            None => return Some(price_data),
+        };
+
+        if Self::is_frozen(e, quote.clone()) {
+            return None;
+        }
+        let quote_data = Self::load_price(e, &quote)?;
+
+        Some(PriceData {
+            price: price_data.price * quote_data.price / 10_i128.pow(PRICE_DECIMALS),
+            timestamp: price_data.timestamp.min(quote_data.timestamp),
+        })
+    }
+
+    /// Get the update held back by the deviation guard for an asset, if any
+    pub fn get_pending_price(e: &Env, asset: Address) -> Option<PendingPrice> {
+        let pending: Map<Address, PendingPrice> = e.storage().instance()
+            .get(&symbol_short!("pending"))
+            .unwrap_or(Map::new(e));
+        pending.get(asset)
+    }
+
+    /// Confirm a pending price. The confirmer must be the admin or an
+    /// authorized reporter other than the one who submitted it.
+    pub fn confirm_price(e: &Env, confirmer: Address, asset: Address) {
+        confirmer.require_auth();
+
+        let mut pending: Map<Address, PendingPrice> = e.storage().instance()
+            .get(&symbol_short!("pending"))
+            .unwrap_or(Map::new(e));
+        let pending_price = pending.get(asset.clone()).expect("No pending price for asset");
+
+        let admin: Address = e.storage().instance().get(&symbol_short!("admin")).unwrap();
+        let is_second_reporter =
+            Self::is_reporter(e, &confirmer) && confirmer != pending_price.reporter;
+        if confirmer != admin && !is_second_reporter {
+            panic!("Unauthorized: confirmation requires admin or a second reporter");
+        }
+
+        pending.remove(asset.clone());
+        e.storage().instance().set(&symbol_short!("pending"), &pending);
+
+        Self::store_price(e, &asset, pending_price.price_data);
+
+        e.events().publish((symbol_short!("price_cnf"), asset), confirmer);
+    }
+
+    /// Discard a pending price (admin-only)
+    pub fn reject_price(e: &Env, asset: Address) {
+        Self::require_admin(e);
+
+        let mut pending: Map<Address, PendingPrice> = e.storage().instance()
+            .get(&symbol_short!("pending"))
+            .unwrap_or(Map::new(e));
+        pending.remove(asset.clone());
+        e.storage().instance().set(&symbol_short!("pending"), &pending);
+
+        e.events().publish((symbol_short!("price_rej"), asset), ());
+    }
+
+    /// Emergency freeze: reads for a frozen asset signal "frozen" instead of a price (admin-only)
+    pub fn set_frozen(e: &Env, asset: Address, frozen: bool) {
+        Self::require_admin(e);
+
+        let mut frozen_assets: Map<Address, bool> = e.storage().instance()
+            .get(&symbol_short!("frozen"))
+            .unwrap_or(Map::new(e));
+        if frozen {
+            frozen_assets.set(asset.clone(), true);
+        } else {
+            frozen_assets.remove(asset.clone());
+        }
+        e.storage().instance().set(&symbol_short!("frozen"), &frozen_assets);
+
+        e.events().publish((symbol_short!("frozen"), asset), frozen);
+    }
+
+    // ========================================================================
+    // Reporters & Quorum
+    // ========================================================================
+
+    /// Register an additional price reporter (admin-only)
+    pub fn add_reporter(e: &Env, reporter: Address) {
+        Self::require_admin(e);
+
+        let mut reporters = Self::get_reporters(e);
+        if reporters.contains(&reporter) {
+            panic!("Reporter already registered");
+        }
+        reporters.push_back(reporter.clone());
+        e.storage().instance().set(&symbol_short!("reporters"), &reporters);
+
+        e.events().publish((symbol_short!("rep_add"),), reporter);
+    }
+
+    /// Remove a price reporter (admin-only)
+    pub fn remove_reporter(e: &Env, reporter: Address) {
+        Self::require_admin(e);
+
+        let mut reporters = Self::get_reporters(e);
+        let index = reporters
+            .first_index_of(&reporter)
+            .expect("Reporter not registered");
+        reporters.remove(index);
+
+        if reporters.len() < Self::get_quorum_config(e).quorum {
+            panic!("Quorum would exceed number of reporters");
+        }
+        e.storage().instance().set(&symbol_short!("reporters"), &reporters);
+
+        e.events().publish((symbol_short!("rep_rem"),), reporter);
+    }
+
+    /// Link an ed25519 signing key to a registered reporter (admin-only)
+    pub fn register_signer(e: &Env, reporter: Address, public_key: BytesN<32>) {
+        Self::require_admin(e);
+
+        if !Self::is_reporter(e, &reporter) {
+            panic!("Reporter not registered");
+        }
+
+        let mut signers: Map<BytesN<32>, Address> = e.storage().instance()
+            .get(&symbol_short!("signers"))
+            .unwrap_or(Map::new(e));
+        signers.set(public_key.clone(), reporter.clone());
+        e.storage().instance().set(&symbol_short!("signers"), &signers);
+
+        e.events().publish((symbol_short!("sig_add"), reporter), public_key);
+    }
+
+    /// Revoke an ed25519 signing key (admin-only)
+    pub fn remove_signer(e: &Env, public_key: BytesN<32>) {
+        Self::require_admin(e);
+
+        let mut signers: Map<BytesN<32>, Address> = e.storage().instance()
+            .get(&symbol_short!("signers"))
+            .unwrap_or(Map::new(e));
+        let reporter = signers.get(public_key.clone()).expect("Unknown signer key");
+        signers.remove(public_key.clone());
+        e.storage().instance().set(&symbol_short!("signers"), &signers);
+
+        e.events().publish((symbol_short!("sig_rem"), reporter), public_key);
+    }
+
+    /// Reporter a signing key submits on behalf of, if registered
+    pub fn get_signer(e: &Env, public_key: BytesN<32>) -> Option<Address> {
+        let signers: Map<BytesN<32>, Address> = e.storage().instance()
+            .get(&symbol_short!("signers"))
+            .unwrap_or(Map::new(e));
+        signers.get(public_key)
+    }
+
+    /// All registered price reporters
+    pub fn get_reporters(e: &Env) -> Vec<Address> {
+        e.storage().instance()
+            .get(&symbol_short!("reporters"))
+            .unwrap_or(Vec::new(e))
+    }
+
+    /// Set how many distinct reporters must submit within `window_seconds`
+    /// before the median price is published (admin-only). The quorum must be
+    /// odd, so the median is always a submitted price.
+    pub fn set_quorum(e: &Env, quorum: u32, window_seconds: u64) {
+        Self::require_admin(e);
+
+        if quorum == 0 || quorum > Self::get_reporters(e).len() {
+            panic!("Quorum must be between 1 and the number of reporters");
+        }
+        if quorum % 2 == 0 {
+            panic!("Quorum must be odd");
+        }
+
+        e.storage().instance().set(&symbol_short!("quorum"), &QuorumConfig {
+            quorum,
+            window_seconds,
+        });
+    }
+
+    /// Current quorum configuration (defaults to a single reporter)
+    pub fn get_quorum_config(e: &Env) -> QuorumConfig {
+        e.storage().instance()
+            .get(&symbol_short!("quorum"))
+            .unwrap_or(QuorumConfig {
+                quorum: 1,
+                window_seconds: RESOLUTION as u64,
+            })
+    }
+
+    /// Reporters whose submissions made up the latest published price for an asset
+    pub fn get_round_reporters(e: &Env, asset: Address) -> Vec<Address> {
+        let contributors: Map<Address, Vec<Address>> = e.storage().instance()
+            .get(&symbol_short!("contribs"))
+            .unwrap_or(Map::new(e));
+        contributors.get(asset).unwrap_or(Vec::new(e))
+    }
+
+    /// Submissions collected so far in the asset's open round
+    pub fn get_open_round(e: &Env, asset: Address) -> Option<Round> {
+        let rounds: Map<Address, Round> = e.storage().instance()
+            .get(&symbol_short!("rounds"))
+            .unwrap_or(Map::new(e));
+        rounds.get(asset)
+    }
+
+    /// Whether an asset's feed is frozen
+    pub fn is_frozen(e: &Env, asset: Address) -> bool {
+        let frozen_assets: Map<Address, bool> = e.storage().instance()
+            .get(&symbol_short!("frozen"))
+            .unwrap_or(Map::new(e));
+        frozen_assets.get(asset).unwrap_or(false)
+    }
+
+    fn exceeds_guard(e: &Env, asset: &Address, price_data: &PriceData) -> bool {
+        let guard = match Self::get_deviation_guard(e, asset.clone()) {
+            Some(guard) => guard,
+            None => return false,
+        };
+
+        if let Some(latest) = Self::load_price(e, asset) {
+            if Self::move_bps(latest.price, price_data.price) > guard.max_update_bps {
+                return true;
+            }
+        }
+
+        let window_start = price_data.timestamp.saturating_sub(guard.window_seconds);
+        if let Some(reference) = Self::history_price_at(e, asset, window_start) {
+            if Self::move_bps(reference.price, price_data.price) > guard.max_window_bps {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn move_bps(from: i128, to: i128) -> i128 {
+        ((to - from).abs() * 10000) / from
+    }
+
+    fn is_reporter(e: &Env, account: &Address) -> bool {
+        Self::get_reporters(e).contains(account)
+    }
+
+    fn require_admin(e: &Env) {
+        let admin: Address = e.storage().instance()
+            .get(&symbol_short!("admin"))
+            .expect("Admin not set");
+        admin.require_auth();
+    }
+
+    // ========================================================================
+    // Price History
+    // ========================================================================
+
+    /// Time-weighted average price over the last `window_seconds`.
+    /// Each observation is weighted by how long it was the latest price within the window.
+    /// History is kept in the quote currency, so derived feeds have no TWAP.
+    pub fn get_twap(e: &Env, asset: Address, window_seconds: u64) -> Option<i128> {
+        if Self::is_frozen(e, asset.clone()) || Self::get_derived_feed(e, asset.clone()).is_some() {
+            return None;
+        }
+
+        let history = Self::load_history(e, &asset);
+        let latest = history.last()?;
+
+        let now = e.ledger().timestamp();
+        let window_start = now.saturating_sub(window_seconds);
+
+        let mut weighted_sum: i128 = 0;
+        let mut total_time: u64 = 0;
+        for i in 0..history.len() {
+            let observation = history.get(i).unwrap();
+            let segment_start = observation.timestamp.max(window_start);
+            let segment_end = if i + 1 < history.len() {
+                history.get(i + 1).unwrap().timestamp
+            } else {
+                now
+            };
+
+            if segment_end <= segment_start {
+                continue;
+            }
+
+            let duration = segment_end - segment_start;
+            weighted_sum += observation.price * duration as i128;
+            total_time += duration;
+        }
+
+        if total_time == 0 {
+            return Some(latest.price);
+        }
+
+        Some(weighted_sum / total_time as i128)
+    }
+
+    /// Price in effect at `timestamp` (the latest observation at or before it).
+    /// None for derived feeds, whose history is in the quote currency.
+    pub fn get_price_at(e: &Env, asset: Address, timestamp: u64) -> Option<PriceData> {
+        if Self::get_derived_feed(e, asset.clone()).is_some() {
+            return None;
+        }
+        Self::history_price_at(e, &asset, timestamp)
+    }
+
+    /// Submitted price in effect at `timestamp`, as stored in the history
+    fn history_price_at(e: &Env, asset: &Address, timestamp: u64) -> Option<PriceData> {
+        let history = Self::load_history(e, asset);
+        let mut result = None;
+        for observation in history.iter() {
+            if observation.timestamp > timestamp {
+                break;
+            }
+            result = Some(observation);
+        }
+        result
+    }
     
     /// Get the latest price for an asset (public, read-only)
     pub fn get_price(e: &Env, asset: Address) -> i128 {
-        // Load prices map - return 0 if not initialized yet
-        let prices: Map<Address, PriceData> = e.storage().instance()
-            .get(&symbol_short!("prices"))
-            .unwrap_or(Map::new(e));
+        if Self::is_frozen(e, asset.clone()) {
+            panic!("Price feed frozen");
+        }
 
         // Get price data for asset - return 0 if not found
-        match prices.get(asset) {
+        match Self::current_price(e, &asset) {
             Some(price_data) => price_data.price,
             None => 0, // Return 0 if no price set yet
         }
@@ -131,18 +1010,105 @@ impl MockOracle {
     
     /// Get the full price data for an asset (price + timestamp)
     pub fn get_price_data(e: &Env, asset: Address) -> (i128, u64) {
-        // Load prices map - return (0, 0) if not initialized yet
-        let prices: Map<Address, PriceData> = e.storage().instance()
-            .get(&symbol_short!("prices"))
-            .unwrap_or(Map::new(e));
+        if Self::is_frozen(e, asset.clone()) {
+            panic!("Price feed frozen");
+        }
 
         // Get price data for asset - return (0, 0) if not found
-        match prices.get(asset) {
+        match Self::current_price(e, &asset) {
             Some(price_data) => (price_data.price, price_data.timestamp),
             None => (0, 0), // Return (0, 0) if no price set yet
         }
     }
     
+    // ========================================================================
+    // SEP-40 Price Feed Interface
+    // ========================================================================
+
+    /// Base asset all prices are quoted in
+    pub fn base(e: &Env) -> Asset {
+        Asset::Other(Symbol::new(e, "USD"))
+    }
+
+    /// All assets in the oracle's registry
+    pub fn assets(e: &Env) -> Vec<Asset> {
+        let registry: Map<Address, AssetInfo> = e.storage().instance()
+            .get(&symbol_short!("registry"))
+            .unwrap_or(Map::new(e));
+
+        let mut assets = Vec::new(e);
+        for asset in registry.keys().iter() {
+            assets.push_back(Asset::Stellar(asset));
+        }
+        assets
+    }
+
+    /// Number of decimals used by all prices in this oracle
+    pub fn decimals(_e: &Env) -> u32 {
+        PRICE_DECIMALS
+    }
+
+    /// Expected seconds between price updates
+    pub fn resolution(_e: &Env) -> u32 {
+        RESOLUTION
+    }
+
+    /// Most recent price for an asset (none while the feed is frozen)
+    pub fn lastprice(e: &Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(address) if !Self::is_frozen(e, address.clone()) => {
+                Self::current_price(e, &address)
+            }
+            _ => None,
+        }
+    }
+
+    /// Price in effect at the given timestamp (none while the feed is frozen,
+    /// or for derived feeds)
+    pub fn price(e: &Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(address) if !Self::is_frozen(e, address.clone()) => {
+                Self::get_price_at(e, address, timestamp)
+            }
+            _ => None,
+        }
+    }
+
+    /// Up to `records` most recent prices for an asset, newest first
+    /// (none while frozen, or for derived feeds)
+    pub fn prices(e: &Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let history = match asset {
+            Asset::Stellar(address)
+                if !Self::is_frozen(e, address.clone())
+                    && Self::get_derived_feed(e, address.clone()).is_none() =>
+            {
+                Self::load_history(e, &address)
+            }
+            _ => return None,
+        };
+        if records == 0 || history.is_empty() {
+            return None;
+        }
+
+        let mut result = Vec::new(e);
+        for observation in history.iter().rev().take(records as usize) {
+            result.push_back(observation);
+        }
+        Some(result)
+    }
+
+    fn load_price(e: &Env, asset: &Address) -> Option<PriceData> {
+        let prices: Map<Address, PriceData> = e.storage().instance()
+            .get(&symbol_short!("prices"))
+            .unwrap_or(Map::new(e));
+        prices.get(asset.clone())
+    }
+
+    /// Get admin address
+    pub fn get_admin(e: &Env) -> Address {
+        e.storage().instance().get(&symbol_short!("admin")).unwrap()
+    }
+
     /// Test helper: Get bot address
     #[cfg(test)]
     pub fn get_bot(e: &Env) -> Address {
@@ -1,12 +1,23 @@
 #![cfg(test)]
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, Symbol,
+};
 
-use crate::contract::{MockOracle, MockOracleClient};
+use crate::contract::{Asset, MockOracle, MockOracleClient};
 
 fn create_oracle_contract<'a>(e: &Env, bot: &Address) -> MockOracleClient<'a> {
-    let address = e.register(MockOracle, (bot,));
+    create_oracle_with_admin(e, &Address::generate(e), bot)
+}
+
+fn create_oracle_with_admin<'a>(e: &Env, admin: &Address, bot: &Address) -> MockOracleClient<'a> {
+    let address = e.register(MockOracle, (admin, bot));
     MockOracleClient::new(e, &address)
 }
 
@@ -21,6 +32,16 @@ fn test_initialization() {
     assert_eq!(client.get_bot(), bot);
 }
 
+#[test]
+fn test_decimals() {
+    let e = Env::default();
+
+    let bot = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    assert_eq!(client.decimals(), 6);
+}
+
 #[test]
 fn test_bot_can_submit_price() {
     let e = Env::default();
@@ -153,3 +174,707 @@ fn test_get_price_for_unknown_asset() {
 
     client.get_price(&asset); // Should panic
 }
+
+#[test]
+fn test_sep40_lastprice() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    assert_eq!(client.lastprice(&Asset::Stellar(asset.clone())), None);
+
+    client.submit_price(&bot, &asset, &1_050_000);
+
+    let price_data = client.lastprice(&Asset::Stellar(asset.clone())).unwrap();
+    assert_eq!(price_data.price, 1_050_000);
+    assert_eq!(client.prices(&Asset::Stellar(asset.clone()), &5).unwrap().len(), 1);
+}
+
+#[test]
+fn test_sep40_metadata() {
+    let e = Env::default();
+
+    let bot = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    assert_eq!(client.base(), Asset::Other(Symbol::new(&e, "USD")));
+    assert_eq!(client.resolution(), 60);
+    assert_eq!(client.lastprice(&Asset::Other(Symbol::new(&e, "EUR"))), None);
+}
+
+#[test]
+fn test_twap() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    assert_eq!(client.get_twap(&asset, &3600), None);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &asset, &100);
+
+    e.ledger().set_timestamp(1_300);
+    client.submit_price(&bot, &asset, &200);
+
+    e.ledger().set_timestamp(1_400);
+
+    // 300s at 100 and 100s at 200
+    assert_eq!(client.get_twap(&asset, &400), Some(125));
+    // Window only covers the latest price
+    assert_eq!(client.get_twap(&asset, &50), Some(200));
+}
+
+#[test]
+fn test_get_price_at() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &asset, &100);
+
+    e.ledger().set_timestamp(2_000);
+    client.submit_price(&bot, &asset, &200);
+
+    assert_eq!(client.get_price_at(&asset, &999), None);
+    assert_eq!(client.get_price_at(&asset, &1_500).unwrap().price, 100);
+    assert_eq!(client.get_price_at(&asset, &2_000).unwrap().price, 200);
+
+    let records = client.prices(&Asset::Stellar(asset), &5).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(0).unwrap().price, 200);
+}
+
+#[test]
+fn test_history_is_bounded() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    for i in 1..=60u64 {
+        e.ledger().set_timestamp(i * 60);
+        client.submit_price(&bot, &asset, &(i as i128));
+    }
+
+    let records = client.prices(&Asset::Stellar(asset.clone()), &100).unwrap();
+    assert_eq!(records.len(), 48);
+    assert_eq!(records.get(47).unwrap().price, 13);
+    assert_eq!(client.get_price_at(&asset, &(12 * 60)), None);
+}
+
+#[test]
+fn test_history_ttl_extended_on_write() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(60);
+    client.submit_price(&bot, &asset, &1_000_000);
+
+    let ttl = e.as_contract(&client.address, || {
+        e.storage()
+            .persistent()
+            .get_ttl(&(symbol_short!("history"), asset.clone()))
+    });
+    assert_eq!(ttl, 518_400);
+}
+
+#[test]
+fn test_out_of_order_price_is_ignored() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(2_000);
+    client.set_price(&asset, &200, &2_000, &bot);
+
+    // A delayed update from before the latest price does not replace it
+    client.set_price(&asset, &100, &1_000, &bot);
+
+    assert_eq!(client.get_price_data(&asset), (200, 2_000));
+    assert_eq!(client.prices(&Asset::Stellar(asset), &5).unwrap().len(), 1);
+}
+
+#[test]
+fn test_deviation_guard_holds_large_move() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.set_deviation_guard(&asset, &1000, &2000, &3600);
+
+    client.submit_price(&bot, &asset, &1_000_000);
+    // 5% move is accepted
+    client.submit_price(&bot, &asset, &1_050_000);
+    assert_eq!(client.get_price(&asset), 1_050_000);
+
+    // 50% move is held as pending
+    client.submit_price(&bot, &asset, &1_575_000);
+    assert_eq!(client.get_price(&asset), 1_050_000);
+    let pending = client.get_pending_price(&asset).unwrap();
+    assert_eq!(pending.price_data.price, 1_575_000);
+    assert_eq!(pending.reporter, bot);
+
+    // Admin confirmation publishes it
+    client.confirm_price(&admin, &asset);
+    assert_eq!(client.get_price(&asset), 1_575_000);
+    assert_eq!(client.get_pending_price(&asset), None);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: confirmation requires admin or a second reporter")]
+fn test_reporter_cannot_confirm_own_price() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.set_deviation_guard(&asset, &1000, &2000, &3600);
+    client.submit_price(&bot, &asset, &1_000_000);
+    client.submit_price(&bot, &asset, &2_000_000);
+
+    client.confirm_price(&bot, &asset);
+}
+
+#[test]
+fn test_frozen_feed_returns_no_price() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.submit_price(&bot, &asset, &1_000_000);
+    client.set_frozen(&asset, &true);
+
+    assert!(client.is_frozen(&asset));
+    assert_eq!(client.lastprice(&Asset::Stellar(asset.clone())), None);
+
+    client.set_frozen(&asset, &false);
+    assert_eq!(
+        client.lastprice(&Asset::Stellar(asset)).unwrap().price,
+        1_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Price feed frozen")]
+fn test_get_price_signals_frozen() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    client.submit_price(&bot, &asset, &1_000_000);
+    client.set_frozen(&asset, &true);
+
+    client.get_price(&asset);
+}
+
+#[test]
+fn test_quorum_publishes_median() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let reporter_b = Address::generate(&e);
+    let reporter_c = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.add_reporter(&reporter_b);
+    client.add_reporter(&reporter_c);
+    client.set_quorum(&3, &60);
+    assert_eq!(client.get_reporters().len(), 3);
+
+    client.submit_price(&bot, &asset, &1_000_000);
+    client.submit_price(&reporter_b, &asset, &1_300_000);
+    assert_eq!(client.lastprice(&Asset::Stellar(asset.clone())), None);
+    assert_eq!(client.get_open_round(&asset).unwrap().submissions.len(), 2);
+
+    client.submit_price(&reporter_c, &asset, &1_020_000);
+
+    assert_eq!(client.get_price(&asset), 1_020_000);
+    assert_eq!(client.get_open_round(&asset), None);
+    assert_eq!(
+        client.get_round_reporters(&asset),
+        vec![&e, bot, reporter_b, reporter_c]
+    );
+}
+
+#[test]
+fn test_quorum_round_expires() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let reporter_b = Address::generate(&e);
+    let reporter_c = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.add_reporter(&reporter_b);
+    client.add_reporter(&reporter_c);
+    client.set_quorum(&3, &60);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &asset, &1_000_000);
+
+    // Round window elapsed: the stale submission is discarded
+    e.ledger().set_timestamp(1_100);
+    client.submit_price(&reporter_b, &asset, &1_100_000);
+    client.submit_price(&reporter_c, &asset, &1_200_000);
+    assert_eq!(client.lastprice(&Asset::Stellar(asset.clone())), None);
+
+    client.submit_price(&bot, &asset, &1_150_000);
+    assert_eq!(client.get_price(&asset), 1_150_000);
+}
+
+#[test]
+#[should_panic(expected = "Quorum must be odd")]
+fn test_even_quorum_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.add_reporter(&Address::generate(&e));
+    client.set_quorum(&2, &60);
+}
+
+#[test]
+fn test_round_uses_oldest_timestamp() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let reporter_b = Address::generate(&e);
+    let reporter_c = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.add_reporter(&reporter_b);
+    client.add_reporter(&reporter_c);
+    client.set_quorum(&3, &60);
+
+    e.ledger().set_timestamp(1_000);
+    client.set_price(&asset, &1_000_000, &940, &bot);
+    client.set_price(&asset, &1_010_000, &990, &reporter_b);
+    client.set_price(&asset, &1_020_000, &1_000, &reporter_c);
+
+    assert_eq!(client.get_price_data(&asset), (1_010_000, 940));
+}
+
+#[test]
+#[should_panic(expected = "Invalid price timestamp")]
+fn test_future_price_timestamp_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(1_000);
+    client.set_price(&asset, &1_000_000, &1_001, &bot);
+}
+
+#[test]
+#[should_panic(expected = "Reporter already submitted this round")]
+fn test_reporter_cannot_submit_twice_per_round() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.add_reporter(&Address::generate(&e));
+    client.add_reporter(&Address::generate(&e));
+    client.set_quorum(&3, &60);
+
+    client.submit_price(&bot, &asset, &1_000_000);
+    client.submit_price(&bot, &asset, &1_500_000);
+}
+
+#[test]
+fn test_per_asset_staleness() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let tbill = Address::generate(&e);
+    let invoice = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    assert!(client.is_stale(&tbill));
+
+    client.set_feed_config(&invoice, &3600);
+    assert_eq!(client.get_feed_config(&tbill).max_age, 86400);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &tbill, &1_000_000);
+    client.submit_price(&bot, &invoice, &980_000);
+
+    e.ledger().set_timestamp(1_000 + 7_200);
+    assert!(!client.is_stale(&tbill));
+    assert!(client.is_stale(&invoice));
+    assert_eq!(client.get_price_checked(&tbill).price, 1_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Price is stale")]
+fn test_get_price_checked_rejects_stale() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.set_feed_config(&asset, &300);
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &asset, &1_000_000);
+
+    e.ledger().set_timestamp(1_301);
+    client.get_price_checked(&asset);
+}
+
+#[test]
+fn test_set_prices_batch() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let tbill = Address::generate(&e);
+    let invoice = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(5_000);
+    client.set_prices(
+        &bot,
+        &vec![
+            &e,
+            (tbill.clone(), 1_010_000i128, 0u64),
+            (invoice.clone(), 970_000i128, 4_990u64),
+        ],
+    );
+
+    assert_eq!(client.get_price_data(&tbill), (1_010_000, 5_000));
+    assert_eq!(client.get_price_data(&invoice), (970_000, 4_990));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: only bot can set prices")]
+fn test_set_prices_requires_reporter() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let attacker = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    client.set_prices(&attacker, &vec![&e, (asset, 1_000_000i128, 0u64)]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid price timestamp")]
+fn test_set_prices_rejects_future_timestamp() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    e.ledger().set_timestamp(1_000);
+    client.set_prices(&bot, &vec![&e, (asset, 1_000_000i128, 5_000u64)]);
+}
+
+#[test]
+fn test_asset_registry() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let tbill = Address::generate(&e);
+    let invoice = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.register_asset(&tbill, &Symbol::new(&e, "TBILL"), &7);
+    client.register_asset(&invoice, &Symbol::new(&e, "INVOICE"), &6);
+    assert_eq!(client.list_assets().len(), 2);
+
+    let info = client.get_asset_info(&tbill).unwrap();
+    assert_eq!(info.symbol, Symbol::new(&e, "TBILL"));
+    assert_eq!(info.decimals, 7);
+
+    // SEP-40 discovery lists registered assets, priced or not
+    client.submit_price(&bot, &invoice, &980_000);
+    let assets = client.assets();
+    assert_eq!(assets.len(), 2);
+    assert!(assets.contains(Asset::Stellar(tbill.clone())));
+    assert!(assets.contains(Asset::Stellar(invoice.clone())));
+
+    client.unregister_asset(&invoice);
+    assert_eq!(client.list_assets().len(), 1);
+    assert_eq!(client.get_asset_info(&invoice), None);
+    assert_eq!(client.assets(), vec![&e, Asset::Stellar(tbill)]);
+}
+
+fn sign_price(
+    e: &Env,
+    key: &SigningKey,
+    oracle: &Address,
+    asset: &Address,
+    price: i128,
+    timestamp: u64,
+    nonce: u64,
+) -> BytesN<64> {
+    let message = (oracle.clone(), asset.clone(), price, timestamp, nonce).to_xdr(e);
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(e, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_relayed_signed_price() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    client.register_signer(&bot, &public_key);
+
+    e.ledger().set_timestamp(2_000);
+    let signature = sign_price(&e, &key, &client.address, &asset, 1_040_000, 1_990, 1);
+    client.submit_signed_price(&asset, &1_040_000, &1_990, &1, &public_key, &signature);
+
+    assert_eq!(client.get_price_data(&asset), (1_040_000, 1_990));
+}
+
+#[test]
+#[should_panic(expected = "Nonce already used")]
+fn test_signed_price_replay_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    client.register_signer(&bot, &public_key);
+
+    e.ledger().set_timestamp(2_000);
+    let signature = sign_price(&e, &key, &client.address, &asset, 1_040_000, 1_990, 1);
+    client.submit_signed_price(&asset, &1_040_000, &1_990, &1, &public_key, &signature);
+    client.submit_signed_price(&asset, &1_040_000, &1_990, &1, &public_key, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_signed_price_tampered_payload_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    client.register_signer(&bot, &public_key);
+
+    e.ledger().set_timestamp(2_000);
+    let signature = sign_price(&e, &key, &client.address, &asset, 1_040_000, 1_990, 1);
+    client.submit_signed_price(&asset, &9_000_000, &1_990, &1, &public_key, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_signed_price_for_other_oracle_rejected() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+    let other = create_oracle_with_admin(&e, &admin, &bot);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    client.register_signer(&bot, &public_key);
+
+    // A payload signed for another oracle cannot be replayed here
+    e.ledger().set_timestamp(2_000);
+    let signature = sign_price(&e, &key, &other.address, &asset, 1_040_000, 1_990, 1);
+    client.submit_signed_price(&asset, &1_040_000, &1_990, &1, &public_key, &signature);
+}
+
+#[test]
+fn test_reserve_attestation() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let rwa_token = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+
+    assert_eq!(client.get_attestation(&rwa_token), None);
+
+    let report_hash = BytesN::from_array(&e, &[1u8; 32]);
+    e.ledger().set_timestamp(1_700_000_000);
+    client.submit_attestation(
+        &bot,
+        &rwa_token,
+        &1_002_000,
+        &5_000_000_000,
+        &Symbol::new(&e, "BNY"),
+        &report_hash,
+        &1_700_000_000,
+    );
+
+    let attestation = client.get_attestation(&rwa_token).unwrap();
+    assert_eq!(attestation.reserve_amount, 5_000_000_000);
+    assert_eq!(attestation.nav, 1_002_000);
+    assert_eq!(attestation.report_hash, report_hash);
+}
+
+#[test]
+#[should_panic(expected = "Attestation older than current report")]
+fn test_reserve_attestation_rejects_older_report() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let rwa_token = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+    let report_hash = BytesN::from_array(&e, &[1u8; 32]);
+    let custodian = Symbol::new(&e, "BNY");
+
+    e.ledger().set_timestamp(300);
+    client.submit_attestation(&bot, &rwa_token, &1_000_000, &100, &custodian, &report_hash, &200);
+    client.submit_attestation(&bot, &rwa_token, &1_000_000, &100, &custodian, &report_hash, &100);
+}
+
+#[test]
+#[should_panic(expected = "Invalid attestation timestamp")]
+fn test_reserve_attestation_rejects_future_timestamp() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bot = Address::generate(&e);
+    let rwa_token = Address::generate(&e);
+    let client = create_oracle_contract(&e, &bot);
+    let report_hash = BytesN::from_array(&e, &[1u8; 32]);
+    let custodian = Symbol::new(&e, "BNY");
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_attestation(&bot, &rwa_token, &1_000_000, &100, &custodian, &report_hash, &1_001);
+}
+
+#[test]
+fn test_derived_feed_price() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let euro_bond = Address::generate(&e);
+    let eur = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.set_derived_feed(&euro_bond, &eur);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &eur, &1_080_000);
+    e.ledger().set_timestamp(1_030);
+    client.submit_price(&bot, &euro_bond, &950_000);
+
+    // 0.95 EUR × 1.08 USD/EUR = 1.026 USD, timestamped by the older component
+    let price = client.lastprice(&Asset::Stellar(euro_bond.clone())).unwrap();
+    assert_eq!(price.price, 1_026_000);
+    assert_eq!(price.timestamp, 1_000);
+    assert_eq!(client.get_price(&euro_bond), 1_026_000);
+
+    // History is in EUR, so it is not served as a USD price
+    let asset = Asset::Stellar(euro_bond.clone());
+    assert_eq!(client.price(&asset, &1_030), None);
+    assert_eq!(client.prices(&asset, &5), None);
+    assert_eq!(client.get_price_at(&euro_bond, &1_030), None);
+    assert_eq!(client.get_twap(&euro_bond, &600), None);
+}
+
+#[test]
+fn test_derived_feed_combined_staleness() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let bot = Address::generate(&e);
+    let euro_bond = Address::generate(&e);
+    let eur = Address::generate(&e);
+    let client = create_oracle_with_admin(&e, &admin, &bot);
+
+    client.set_derived_feed(&euro_bond, &eur);
+    client.set_feed_config(&eur, &600);
+
+    e.ledger().set_timestamp(1_000);
+    client.submit_price(&bot, &eur, &1_080_000);
+    e.ledger().set_timestamp(1_700);
+    client.submit_price(&bot, &euro_bond, &950_000);
+
+    // The bond's own price is fresh but the EUR/USD leg is not
+    assert!(client.is_stale(&euro_bond));
+
+    client.submit_price(&bot, &eur, &1_070_000);
+    assert!(!client.is_stale(&euro_bond));
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Map, Symbol, contracttype, IntoVal};
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Map, Symbol, Vec, contracttype, IntoVal};
 
 // RWA token client interface
 pub struct RwaTokenClient<'a> {
@@ -19,6 +19,14 @@ impl<'a> RwaTokenClient<'a> {
         )
     }
 
+    pub fn has_role(&self, account: &Address, role: &Symbol) -> Option<u32> {
+        self.env.invoke_contract(
+            self.address,
+            &Symbol::new(self.env, "has_role"),
+            (account, role).into_val(self.env),
+        )
+    }
+
     pub fn allow_user(&self, user: &Address) {
         let vault_address = self.env.current_contract_address();
         self.env.invoke_contract::<()>(
@@ -77,13 +85,74 @@ impl<'a> StRwaTokenClient<'a> {
 #[contracttype]
 #[derive(Clone)]
 pub struct StakeInfo {
-    pub amount: i128,              // Amount of RWA staked
+    pub amount: i128,              // stRWA minted for the user's stake
     pub timestamp: u64,            // When they staked
     pub is_borrower: bool,         // Are they using stRWA as collateral?
     pub borrowed_amount: i128,     // How much USDC borrowed (if borrower)
     pub loan_period: u64,          // Total loan duration in seconds
+    pub auto_compound: bool,       // Reinvest USDC yield into the stake
+}
+
+/// Borrower exit rules for a vault
+#[contracttype]
+#[derive(Clone)]
+pub struct ExitConfig {
+    pub lockup_bps: u32,             // Share of the loan period before a borrower may unstake
+    pub foreclosure_fee_bps: u32,    // Fee on stRWA unstaked while a loan is outstanding
+    pub treasury: Option<Address>,   // Fee recipient; required for a non-zero fee
+}
+
+const MAX_FORECLOSURE_FEE_BPS: u32 = 2000;
+
+/// Fees charged for running the vault
+#[contracttype]
+#[derive(Clone)]
+pub struct FeeConfig {
+    pub management_fee_bps: u32,    // Annual fee on total_assets, minted as stRWA
+    pub performance_fee_bps: u32,   // Cut of distributed yield, kept in the reward token
+    pub recipient: Option<Address>, // Receives both fees
+}
+
+const MAX_MANAGEMENT_FEE_BPS: u32 = 500;
+const MAX_PERFORMANCE_FEE_BPS: u32 = 3000;
+
+/// RWA locked for a user until an unstake cooldown ends
+#[contracttype]
+#[derive(Clone)]
+pub struct UnstakeRequest {
+    pub id: u32,
+    pub shares: i128,       // stRWA burned for the request
+    pub assets: i128,       // RWA owed once unlocked
+    pub unlock_time: u64,
+}
+
+/// Per-user checkpoint for reward-per-share yield accounting
+#[contracttype]
+#[derive(Clone)]
+pub struct RewardCheckpoint {
+    pub balance: i128,                             // stRWA earning yield (includes collateral held by the pool)
+    pub reward_per_share_paid: Map<Address, i128>, // Accumulator per reward token at the last settlement
+    pub accrued: Map<Address, i128>,               // Rewards settled but not yet claimed, per token
+}
+
+/// Yield funded for linear release between `start` and `end`
+#[contracttype]
+#[derive(Clone)]
+pub struct YieldStream {
+    pub amount: i128,
+    pub start: u64,
+    pub end: u64,
+    pub released: i128,   // Portion already added to the reward-per-share accumulator
 }
 
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+/// One whole stRWA (18 decimals)
+const STRWA_UNIT: i128 = 1_000_000_000_000_000_000;
+
+/// Scale of the reward-per-share accumulator
+const REWARD_PRECISION: i128 = 1_000_000_000_000_000_000;
+
 /// Storage Keys
 const RWA_TOKEN_KEY: Symbol = symbol_short!("rwa");
 const STRWA_TOKEN_KEY: Symbol = symbol_short!("strwa");
@@ -91,9 +160,26 @@ const USDC_TOKEN_KEY: Symbol = symbol_short!("usdc");
 const LENDING_POOL_KEY: Symbol = symbol_short!("pool");
 const ADMIN_KEY: Symbol = symbol_short!("admin");
 
-const TOTAL_YIELD_POOL: Symbol = symbol_short!("yield");
+const TOTAL_YIELD_POOL: Symbol = symbol_short!("yield");   // Map<Address, i128> per reward token
 const STAKE_INFO: Symbol = symbol_short!("stakes");      // Map<Address, StakeInfo>
 const LP_LIQUIDITY_USED: Symbol = symbol_short!("lp_used"); // Map<Address, i128>
+const REWARD_TOKENS: Symbol = symbol_short!("rtokens"); // Vec<Address>
+const REWARD_PER_SHARE: Symbol = symbol_short!("rps");   // Map<Address, i128> per reward token
+const REWARDS: Symbol = symbol_short!("rewards");        // Map<Address, RewardCheckpoint>
+const UNDISTRIBUTED_YIELD: Symbol = symbol_short!("undist"); // Map<Address, i128> per reward token
+const YIELD_STREAMS: Symbol = symbol_short!("streams"); // Map<Address, Vec<YieldStream>>
+const UNSTAKE_COOLDOWN: Symbol = symbol_short!("cooldown");
+const EXIT_CONFIG: Symbol = symbol_short!("exit_cfg");
+const UNSTAKE_REQUESTS: Symbol = symbol_short!("unstk_req"); // Map<Address, Vec<UnstakeRequest>>
+const NEXT_REQUEST_ID: Symbol = symbol_short!("req_id");
+const PENDING_UNSTAKE: Symbol = symbol_short!("pend_unst");
+const FEE_CONFIG: Symbol = symbol_short!("fee_cfg");
+const FEE_ACCRUED_AT: Symbol = symbol_short!("fee_ts");
+const ACCRUED_FEES: Symbol = symbol_short!("fees");      // Map<Address, i128> performance fees per reward token
+const COMPOUND_PRICE: Symbol = symbol_short!("cmp_price"); // USDC per whole RWA
+const SEIZED_RWA: Symbol = symbol_short!("seized");      // Map<Address, i128> RWA earmarked for seizure recipients
+const SEIZED_TOTAL: Symbol = symbol_short!("seized_t");
+const TOTAL_SHARES: Symbol = symbol_short!("shares");   // stRWA supply, tracked here since the vault mints and burns all of it
 
 #[contract]
 pub struct RwaVault;
@@ -111,8 +197,9 @@ impl RwaVault {
         e.storage().instance().set(&RWA_TOKEN_KEY, &rwa_token);
         e.storage().instance().set(&STRWA_TOKEN_KEY, &strwa_token);
         
-        // Initialize yield pool to 0
-        e.storage().instance().set(&TOTAL_YIELD_POOL, &0i128);
+        // Initialize reward reserves per token
+        let reward_reserves: Map<Address, i128> = Map::new(e);
+        e.storage().instance().set(&TOTAL_YIELD_POOL, &reward_reserves);
         
         // Initialize stake info map
         let stakes: Map<Address, StakeInfo> = Map::new(e);
@@ -133,6 +220,9 @@ impl RwaVault {
         }
         
         e.storage().instance().set(&USDC_TOKEN_KEY, &usdc);
+
+        // USDC is the default reward token
+        Self::register_reward_token(e, &usdc);
     }
 
     /// Set LendingPool address (one-time, after LendingPool deployment)
@@ -147,284 +237,1023 @@ impl RwaVault {
         e.storage().instance().set(&LENDING_POOL_KEY, &lending_pool);
     }
 
+    /// Stake RWA for stRWA at the current exchange rate (same as `deposit` to self)
     pub fn stake(e: &Env, user: Address, amount: i128) {
         user.require_auth();
-        
-        if amount <= 0 {
+        Self::accrue_management_fee(e);
+
+        let shares = Self::preview_deposit(e, amount);
+        Self::enter(e, &user, &user, amount, shares);
+    }
+
+    /// Burn `amount` stRWA for the underlying RWA (same as `redeem` to self)
+    pub fn unstake(e: &Env, user: Address, amount: i128) {
+        user.require_auth();
+        Self::accrue_management_fee(e);
+
+        let assets = Self::preview_redeem(e, amount);
+        Self::exit(e, &user, &user, amount, assets);
+    }
+
+    // ========================================================================
+    // Tokenized Vault (ERC-4626 style)
+    // ========================================================================
+
+    /// Deposit exactly `assets` RWA from `from`, minting stRWA to `receiver`
+    pub fn deposit(e: &Env, from: Address, assets: i128, receiver: Address) -> i128 {
+        from.require_auth();
+        Self::accrue_management_fee(e);
+
+        let shares = Self::preview_deposit(e, assets);
+        Self::enter(e, &from, &receiver, assets, shares);
+        shares
+    }
+
+    /// Mint exactly `shares` stRWA to `receiver`, pulling the required RWA from `from`
+    pub fn mint(e: &Env, from: Address, shares: i128, receiver: Address) -> i128 {
+        from.require_auth();
+        Self::accrue_management_fee(e);
+
+        let assets = Self::preview_mint(e, shares);
+        Self::enter(e, &from, &receiver, assets, shares);
+        assets
+    }
+
+    /// Withdraw exactly `assets` RWA to `receiver`, burning the required stRWA from `owner`
+    pub fn withdraw(e: &Env, owner: Address, assets: i128, receiver: Address) -> i128 {
+        owner.require_auth();
+        Self::accrue_management_fee(e);
+
+        let shares = Self::preview_withdraw(e, assets);
+        Self::exit(e, &owner, &receiver, shares, assets);
+        shares
+    }
+
+    /// Redeem exactly `shares` stRWA from `owner`, sending the RWA to `receiver`
+    pub fn redeem(e: &Env, owner: Address, shares: i128, receiver: Address) -> i128 {
+        owner.require_auth();
+        Self::accrue_management_fee(e);
+
+        let assets = Self::preview_redeem(e, shares);
+        Self::exit(e, &owner, &receiver, shares, assets);
+        assets
+    }
+
+    /// RWA held by the vault backing all stRWA (excluding RWA held as rewards,
+    /// locked for pending unstake requests or earmarked from seized collateral)
+    pub fn total_assets(e: &Env) -> i128 {
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        let pending_unstake: i128 = e.storage().instance().get(&PENDING_UNSTAKE).unwrap_or(0);
+        let seized: i128 = e.storage().instance().get(&SEIZED_TOTAL).unwrap_or(0);
+        token::Client::new(e, &rwa_addr).balance(&e.current_contract_address())
+            - Self::get_reward_reserve(e, rwa_addr)
+            - pending_unstake
+            - seized
+    }
+
+    /// stRWA that `assets` RWA is worth at the current exchange rate
+    pub fn convert_to_shares(e: &Env, assets: i128) -> i128 {
+        Self::mul_div(assets, Self::total_shares(e) + 1, Self::total_assets(e) + 1, false)
+    }
+
+    /// RWA that `shares` stRWA is worth at the current exchange rate
+    pub fn convert_to_assets(e: &Env, shares: i128) -> i128 {
+        Self::mul_div(shares, Self::total_assets(e) + 1, Self::total_shares(e) + 1, false)
+    }
+
+    /// stRWA minted by `deposit(assets)` (rounds down)
+    pub fn preview_deposit(e: &Env, assets: i128) -> i128 {
+        Self::convert_to_shares(e, assets)
+    }
+
+    /// RWA pulled by `mint(shares)` (rounds up)
+    pub fn preview_mint(e: &Env, shares: i128) -> i128 {
+        Self::mul_div(shares, Self::total_assets(e) + 1, Self::total_shares(e) + 1, true)
+    }
+
+    /// stRWA burned by `withdraw(assets)` (rounds up)
+    pub fn preview_withdraw(e: &Env, assets: i128) -> i128 {
+        Self::mul_div(assets, Self::total_shares(e) + 1, Self::total_assets(e) + 1, true)
+    }
+
+    /// RWA returned by `redeem(shares)` (rounds down)
+    pub fn preview_redeem(e: &Env, shares: i128) -> i128 {
+        Self::convert_to_assets(e, shares)
+    }
+
+    /// stRWA supply as tracked by the vault. Read from storage rather than the
+    /// token, so it is safe to use inside the stRWA transfer hook.
+    fn total_shares(e: &Env) -> i128 {
+        e.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
+    }
+
+    fn mint_shares(e: &Env, to: &Address, shares: i128) {
+        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
+        StRwaTokenClient::new(e, &strwa_addr).mint(to, &shares);
+        e.storage().instance().set(&TOTAL_SHARES, &(Self::total_shares(e) + shares));
+    }
+
+    fn burn_shares(e: &Env, from: &Address, shares: i128) {
+        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
+        StRwaTokenClient::new(e, &strwa_addr).burn(from, &shares);
+        e.storage().instance().set(&TOTAL_SHARES, &(Self::total_shares(e) - shares));
+    }
+
+    /// Align the tracked stRWA supply with the token (admin-only), for vaults
+    /// upgraded from a version that read the supply from stRWA
+    pub fn sync_total_shares(e: &Env) -> i128 {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
+        let supply = StRwaTokenClient::new(e, &strwa_addr).total_supply();
+        e.storage().instance().set(&TOTAL_SHARES, &supply);
+        supply
+    }
+
+    fn mul_div(x: i128, y: i128, denominator: i128, round_up: bool) -> i128 {
+        let product = x * y;
+        if round_up {
+            (product + denominator - 1) / denominator
+        } else {
+            product / denominator
+        }
+    }
+
+    /// Pull `assets` RWA from `from` into custody and mint `shares` stRWA to `receiver`
+    fn enter(e: &Env, from: &Address, receiver: &Address, assets: i128, shares: i128) {
+        if assets <= 0 {
             panic!("Amount must be positive");
         }
-        
+        if shares <= 0 {
+            panic!("Deposit too small to mint shares");
+        }
+
         let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
-        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
 
-        // let rwa_token_client = RwaTokenClient::new(e, &rwa_addr);
+        let rwa_token_client = RwaTokenClient::new(e, &rwa_addr);
+
+        // Check if user is allowed to hold RWA tokens
+        if !rwa_token_client.allowed(from) {
+            // If not, the vault allows them when it holds the manager role
+            let vault_address = e.current_contract_address();
+            if rwa_token_client.has_role(&vault_address, &symbol_short!("manager")).is_none() {
+                panic!("User is not on the RWA allowlist");
+            }
+            rwa_token_client.allow_user(from);
+        }
+
+        // Take custody of the RWA; shares are only minted for what was received
+        let rwa_token = token::Client::new(e, &rwa_addr);
+        let balance_before = rwa_token.balance(&e.current_contract_address());
+        rwa_token.transfer(
+            from,
+            e.current_contract_address(),
+            &assets
+        );
+        if rwa_token.balance(&e.current_contract_address()) - balance_before < assets {
+            panic!("RWA transfer shortfall");
+        }
 
-        // // Check if user is allowed to hold RWA tokens
-        // // if !rwa_token_client.allowed(&user) {
-        // //     // If not, the vault (as manager) allows them
-        // //     rwa_token_client.allow_user(&user);
-        // // }
+        Self::credit_shares(e, receiver, shares);
         
-        // let rwa_token = token::Client::new(e, &rwa_addr);
-        // // rwa_token.transfer(
-        // //     &user,
-        // //     &e.current_contract_address(),
-        // //     &amount
-        // // );
+        e.events().publish(
+            (symbol_short!("deposit"), from.clone(), receiver.clone()),
+            (assets, shares)
+        );
+    }
+
+    /// Mint `shares` stRWA to `receiver` and add them to its stake
+    fn credit_shares(e: &Env, receiver: &Address, shares: i128) {
+        // Release streamed yield to current holders before supply changes
+        Self::accrue_streams(e);
 
-        let strwa_client = StRwaTokenClient::new(e, &strwa_addr);
-        strwa_client.mint(&user, &amount);
+        Self::mint_shares(e, receiver, shares);
+        Self::adjust_reward_balance(e, receiver, shares);
         
         let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
             .get(&STAKE_INFO)
             .unwrap_or(Map::new(e));
         
-        let existing_stake = stakes.get(user.clone()).unwrap_or(StakeInfo {
+        let existing_stake = stakes.get(receiver.clone()).unwrap_or(StakeInfo {
             amount: 0,
             timestamp: 0,
             is_borrower: false,
             borrowed_amount: 0,
             loan_period: 0,
+            auto_compound: false,
         });
         
-        stakes.set(user.clone(), StakeInfo {
-            amount: existing_stake.amount + amount,
+        stakes.set(receiver.clone(), StakeInfo {
+            amount: existing_stake.amount + shares,
             timestamp: e.ledger().timestamp(),
             is_borrower: existing_stake.is_borrower,
             borrowed_amount: existing_stake.borrowed_amount,
             loan_period: existing_stake.loan_period,
+            auto_compound: existing_stake.auto_compound,
         });
         
         e.storage().instance().set(&STAKE_INFO, &stakes);
-        
-        e.events().publish((symbol_short!("stake"), user.clone()), amount);
     }
 
-    pub fn unstake(e: &Env, user: Address, amount: i128) {
-        user.require_auth();
+    /// Burn `shares` stRWA from `owner` and send `assets` RWA to `receiver` immediately
+    fn exit(e: &Env, owner: &Address, receiver: &Address, shares: i128, assets: i128) {
+        if Self::get_unstake_cooldown(e) > 0 {
+            panic!("Unstake cooldown active: use request_unstake");
+        }
+
+        Self::release_shares(e, owner, shares);
+
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        let rwa_token = token::Client::new(e, &rwa_addr);
+        rwa_token.transfer(
+            &e.current_contract_address(),
+            receiver,
+            &assets
+        );
         
-        if amount <= 0 {
+        e.events().publish(
+            (symbol_short!("withdraw"), owner.clone(), receiver.clone()),
+            (assets, shares)
+        );
+    }
+
+    /// Burn `shares` stRWA from `owner` and remove them from its stake,
+    /// enforcing borrower lockup, foreclosure fee and LP liquidity rules
+    fn release_shares(e: &Env, owner: &Address, shares: i128) {
+        if shares <= 0 {
             panic!("Amount must be positive");
         }
+
+        Self::accrue_streams(e);
         
         let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
             .get(&STAKE_INFO)
             .unwrap();
         
-        let stake_info = stakes.get(user.clone())
+        let stake_info = stakes.get(owner.clone())
             .expect("User has no stake");
         
-        if amount > stake_info.amount {
+        if shares > stake_info.amount {
             panic!("Insufficient staked amount");
         }
         
-        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
+        let config = Self::get_exit_config(e);
+
+        // stRWA released from the user's stake, including any foreclosure fee
+        let mut released = shares;
+        let mut foreclosure_fee = 0;
 
         if stake_info.is_borrower {
             let current_time = e.ledger().timestamp();
             let time_elapsed = current_time - stake_info.timestamp;
-            let lockup_period = (stake_info.loan_period * 20) / 100;
+            let lockup_period = (stake_info.loan_period * config.lockup_bps as u64) / 10000;
             
             if time_elapsed < lockup_period {
-                panic!("Cannot unstake during lockup period (first 20% of loan)");
+                panic!("Cannot unstake during lockup period");
             }
             
             if stake_info.borrowed_amount > 0 {
-                let foreclosure_fee = (amount * 5) / 100;
+                foreclosure_fee = (shares * config.foreclosure_fee_bps as i128) / 10000;
+                released += foreclosure_fee;
 
-                let strwa_client = StRwaTokenClient::new(e, &strwa_addr);
-                strwa_client.burn(&user, &foreclosure_fee);
-
-                e.events().publish(
-                    (symbol_short!("forclose"), user.clone()),
-                    foreclosure_fee
-                );
+                if released > stake_info.amount {
+                    panic!("Insufficient staked amount to cover foreclosure fee");
+                }
             }
         } else {
             let lp_used: Map<Address, i128> = e.storage().instance()
                 .get(&LP_LIQUIDITY_USED)
                 .unwrap_or(Map::new(e));
             
-            let liquidity_in_use = lp_used.get(user.clone()).unwrap_or(0);
+            let liquidity_in_use = lp_used.get(owner.clone()).unwrap_or(0);
             
-            if liquidity_in_use > 0 && amount > (stake_info.amount - liquidity_in_use) {
+            if liquidity_in_use > 0 && shares > (stake_info.amount - liquidity_in_use) {
                 panic!("Cannot unstake: liquidity is being used for loans");
             }
         }
 
-        let strwa_client = StRwaTokenClient::new(e, &strwa_addr);
-        strwa_client.burn(&user, &amount);
-
-        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
-        let rwa_token = token::Client::new(e, &rwa_addr);
-        rwa_token.transfer(
-            &e.current_contract_address(),
-            &user,
-            &amount
-        );
+        Self::burn_shares(e, owner, released);
+        Self::adjust_reward_balance(e, owner, -released);
         
-        stakes.set(user.clone(), StakeInfo {
-            amount: stake_info.amount - amount,
+        stakes.set(owner.clone(), StakeInfo {
+            amount: stake_info.amount - released,
             timestamp: stake_info.timestamp,
             is_borrower: stake_info.is_borrower,
             borrowed_amount: stake_info.borrowed_amount,
             loan_period: stake_info.loan_period,
+            auto_compound: stake_info.auto_compound,
         });
         
         e.storage().instance().set(&STAKE_INFO, &stakes);
-        
-        e.events().publish((symbol_short!("unstake"), user.clone()), amount);
+
+        if foreclosure_fee > 0 {
+            // The fee moves to the treasury rather than being destroyed
+            let treasury = config.treasury.expect("Treasury required");
+            Self::credit_shares(e, &treasury, foreclosure_fee);
+
+            e.events().publish(
+                (symbol_short!("forclose"), owner.clone(), treasury),
+                (foreclosure_fee, shares, stake_info.borrowed_amount)
+            );
+        }
     }
 
-    pub fn admin_fund_yield(e: &Env, amount: i128) {
+    // ========================================================================
+    // Exit Rules
+    // ========================================================================
+
+    /// Configure the borrower lockup (fraction of the loan period), the
+    /// foreclosure fee on early exits, and the treasury it is paid to (admin-only).
+    pub fn set_exit_config(
+        e: &Env,
+        lockup_bps: u32,
+        foreclosure_fee_bps: u32,
+        treasury: Option<Address>,
+    ) {
         let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
         admin.require_auth();
-        
-        if amount <= 0 {
-            panic!("Amount must be positive");
+
+        if lockup_bps > 10000 {
+            panic!("Lockup cannot exceed the loan period");
         }
-        
-        let usdc_addr: Address = e.storage().instance().get(&USDC_TOKEN_KEY).unwrap();
-        let usdc_token = token::Client::new(e, &usdc_addr);
-        usdc_token.transfer_from(
-            &e.current_contract_address(),
-            &admin,
-            &e.current_contract_address(),
-            &amount
+        if foreclosure_fee_bps > MAX_FORECLOSURE_FEE_BPS {
+            panic!("Foreclosure fee too high");
+        }
+        if treasury.is_none() && foreclosure_fee_bps > 0 {
+            panic!("Treasury required");
+        }
+
+        let config = ExitConfig {
+            lockup_bps,
+            foreclosure_fee_bps,
+            treasury,
+        };
+        e.storage().instance().set(&EXIT_CONFIG, &config);
+
+        e.events().publish(
+            (symbol_short!("exit_cfg"), admin),
+            (lockup_bps, foreclosure_fee_bps, config.treasury)
         );
-        
-        let current_yield: i128 = e.storage().instance().get(&TOTAL_YIELD_POOL).unwrap_or(0);
-        e.storage().instance().set(&TOTAL_YIELD_POOL, &(current_yield + amount));
-        
-        e.events().publish((symbol_short!("yieldfund"), admin), amount);
     }
 
-    pub fn claimable_yield(e: &Env, user: Address) -> i128 {
-        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
-        let strwa_client = StRwaTokenClient::new(e, &strwa_addr);
+    /// Exit rules (defaults: 20% lockup, no fee until a treasury is configured)
+    pub fn get_exit_config(e: &Env) -> ExitConfig {
+        e.storage().instance().get(&EXIT_CONFIG).unwrap_or(ExitConfig {
+            lockup_bps: 2000,
+            foreclosure_fee_bps: 0,
+            treasury: None,
+        })
+    }
 
-        let user_balance = strwa_client.balance(&user);
+    // ========================================================================
+    // Vault Fees
+    // ========================================================================
 
-        if user_balance == 0 {
-            return 0;
+    /// Set the annual management fee, the performance fee on yield and their
+    /// recipient (admin-only). Management fees owed at the old rate are minted first.
+    pub fn set_fee_config(
+        e: &Env,
+        management_fee_bps: u32,
+        performance_fee_bps: u32,
+        recipient: Option<Address>,
+    ) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        if management_fee_bps > MAX_MANAGEMENT_FEE_BPS {
+            panic!("Management fee too high");
+        }
+        if performance_fee_bps > MAX_PERFORMANCE_FEE_BPS {
+            panic!("Performance fee too high");
+        }
+        if recipient.is_none() && (management_fee_bps > 0 || performance_fee_bps > 0) {
+            panic!("Fee recipient required");
         }
 
-        let total_shares = strwa_client.total_supply();
+        Self::accrue_management_fee(e);
 
-        if total_shares == 0 {
-            return 0;
-        }
-        
-        let total_yield: i128 = e.storage().instance().get(&TOTAL_YIELD_POOL).unwrap_or(0);
-        
-        (total_yield * user_balance) / total_shares
-    }
+        let config = FeeConfig {
+            management_fee_bps,
+            performance_fee_bps,
+            recipient,
+        };
+        e.storage().instance().set(&FEE_CONFIG, &config);
 
-    pub fn claim_yield(e: &Env, user: Address) -> i128 {
-        user.require_auth();
-        
-        let claimable = Self::claimable_yield(e, user.clone());
-        
-        if claimable == 0 {
-            panic!("No yield to claim");
-        }
-        
-        let usdc_addr: Address = e.storage().instance().get(&USDC_TOKEN_KEY).unwrap();
-        let usdc_token = token::Client::new(e, &usdc_addr);
-        usdc_token.transfer(
-            &e.current_contract_address(),
-            &user,
-            &claimable
+        e.events().publish(
+            (symbol_short!("fee_cfg"), admin),
+            (management_fee_bps, performance_fee_bps, config.recipient)
         );
-        
-        let current_yield: i128 = e.storage().instance().get(&TOTAL_YIELD_POOL).unwrap();
-        e.storage().instance().set(&TOTAL_YIELD_POOL, &(current_yield - claimable));
-        
-        e.events().publish((symbol_short!("claim"), user.clone()), claimable);
-        
-        claimable
     }
 
-    pub fn mark_as_borrower(
-        e: &Env,
-        user: Address,
-        borrowed_amount: i128,
-        loan_period: u64
-    ) {
-        let lending_pool: Address = e.storage().instance()
-            .get(&LENDING_POOL_KEY)
-            .expect("Lending pool not set");
-        lending_pool.require_auth();
-        
-        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
-            .get(&STAKE_INFO)
-            .unwrap();
-        
-        let mut stake_info = stakes.get(user.clone())
-            .expect("User has no stake");
-        
-        stake_info.is_borrower = true;
-        stake_info.borrowed_amount = borrowed_amount;
-        stake_info.loan_period = loan_period;
-        stake_info.timestamp = e.ledger().timestamp();
-        
-        stakes.set(user.clone(), stake_info);
-        e.storage().instance().set(&STAKE_INFO, &stakes);
+    /// Fee schedule (defaults to no fees)
+    pub fn get_fee_config(e: &Env) -> FeeConfig {
+        e.storage().instance().get(&FEE_CONFIG).unwrap_or(FeeConfig {
+            management_fee_bps: 0,
+            performance_fee_bps: 0,
+            recipient: None,
+        })
     }
 
-    pub fn pull_yield_for_repay(e: &Env, user: Address, amount: i128) -> i128 {
-        let lending_pool: Address = e.storage().instance()
-            .get(&LENDING_POOL_KEY)
-            .expect("Lending pool not set");
-        lending_pool.require_auth();
-        
-        let claimable = Self::claimable_yield(e, user.clone());
-        let amount_to_pull = if amount > claimable { claimable } else { amount };
-        
-        if amount_to_pull == 0 {
-            return 0;
+    /// Mint the management fee owed since the last accrual to the fee
+    /// recipient. Permissionless; returns the stRWA minted.
+    pub fn accrue_management_fee(e: &Env) -> i128 {
+        let shares = Self::accrued_management_fee(e);
+
+        // A fee too small to mint a share keeps accruing from the last mint;
+        // nothing is owed while the rate is zero or the vault is empty
+        let idle = Self::get_fee_config(e).management_fee_bps == 0
+            || Self::total_shares(e) == 0
+            || Self::total_assets(e) == 0;
+        if shares > 0 || idle {
+            e.storage().instance().set(&FEE_ACCRUED_AT, &e.ledger().timestamp());
         }
-        
-        let usdc_addr: Address = e.storage().instance().get(&USDC_TOKEN_KEY).unwrap();
-        let usdc_token = token::Client::new(e, &usdc_addr);
-        usdc_token.transfer(
-            &e.current_contract_address(),
-            &lending_pool,
-            &amount_to_pull
-        );
-        
-        let current_yield: i128 = e.storage().instance().get(&TOTAL_YIELD_POOL).unwrap();
-        e.storage().instance().set(&TOTAL_YIELD_POOL, &(current_yield - amount_to_pull));
-        
-        amount_to_pull
+
+        if shares > 0 {
+            let recipient = Self::get_fee_config(e).recipient.unwrap();
+            Self::credit_shares(e, &recipient, shares);
+
+            e.events().publish((symbol_short!("mgmt_fee"), recipient), shares);
+        }
+        shares
     }
 
-    pub fn update_borrowed_amount(e: &Env, user: Address, new_amount: i128) {
-        let lending_pool: Address = e.storage().instance()
-            .get(&LENDING_POOL_KEY)
-            .expect("Lending pool not set");
-        lending_pool.require_auth();
-        
-        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
-            .get(&STAKE_INFO)
-            .unwrap();
-        
-        let mut stake_info = stakes.get(user.clone())
-            .expect("User has no stake");
-        
-        stake_info.borrowed_amount = new_amount;
-        
-        if new_amount == 0 {
-            stake_info.is_borrower = false;
+    /// stRWA the management fee owed since the last accrual would mint
+    pub fn accrued_management_fee(e: &Env) -> i128 {
+        let config = Self::get_fee_config(e);
+        let last: u64 = e.storage().instance()
+            .get(&FEE_ACCRUED_AT)
+            .unwrap_or(e.ledger().timestamp());
+        let elapsed = (e.ledger().timestamp() - last) as i128;
+
+        let total_shares = Self::total_shares(e);
+        if config.management_fee_bps == 0 || elapsed == 0 || total_shares == 0 {
+            return 0;
         }
-        
-        stakes.set(user.clone(), stake_info);
-        e.storage().instance().set(&STAKE_INFO, &stakes);
+
+        let total_assets = Self::total_assets(e);
+        let fee_assets = (total_assets * config.management_fee_bps as i128 * elapsed)
+            / (10000 * SECONDS_PER_YEAR);
+        if fee_assets <= 0 || fee_assets >= total_assets {
+            return 0;
+        }
+
+        // Dilute holders so the new shares are worth `fee_assets` afterwards
+        Self::mul_div(fee_assets, total_shares + 1, total_assets - fee_assets + 1, false)
     }
 
-    pub fn set_lp_liquidity_used(e: &Env, lp: Address, amount_used: i128) {
-        let lending_pool: Address = e.storage().instance()
-            .get(&LENDING_POOL_KEY)
+    /// Performance fees of a reward token held for the fee recipient
+    pub fn get_accrued_fees(e: &Env, reward_token: Address) -> i128 {
+        let fees: Map<Address, i128> = e.storage().instance()
+            .get(&ACCRUED_FEES)
+            .unwrap_or(Map::new(e));
+        fees.get(reward_token).unwrap_or(0)
+    }
+
+    /// Pay accrued performance fees of a reward token to the fee recipient
+    pub fn claim_fees(e: &Env, reward_token: Address) -> i128 {
+        let recipient = Self::get_fee_config(e).recipient.expect("Fee recipient not set");
+        recipient.require_auth();
+
+        Self::accrue_streams(e);
+
+        let amount = Self::get_accrued_fees(e, reward_token.clone());
+        if amount == 0 {
+            panic!("No fees to claim");
+        }
+
+        let mut fees: Map<Address, i128> = e.storage().instance()
+            .get(&ACCRUED_FEES)
+            .unwrap_or(Map::new(e));
+        fees.remove(reward_token.clone());
+        e.storage().instance().set(&ACCRUED_FEES, &fees);
+
+        Self::pay_reward(e, &reward_token, &recipient, amount);
+
+        e.events().publish((symbol_short!("fee_claim"), recipient, reward_token), amount);
+
+        amount
+    }
+
+    fn performance_fee(e: &Env, amount: i128) -> i128 {
+        (amount * Self::get_fee_config(e).performance_fee_bps as i128) / 10000
+    }
+
+    /// Set aside the performance fee from yield being distributed; returns what stakers get
+    fn take_performance_fee(e: &Env, reward_token: &Address, amount: i128) -> i128 {
+        let fee = Self::performance_fee(e, amount);
+        if fee == 0 {
+            return amount;
+        }
+
+        let mut fees: Map<Address, i128> = e.storage().instance()
+            .get(&ACCRUED_FEES)
+            .unwrap_or(Map::new(e));
+        let accrued = fees.get(reward_token.clone()).unwrap_or(0);
+        fees.set(reward_token.clone(), accrued + fee);
+        e.storage().instance().set(&ACCRUED_FEES, &fees);
+
+        amount - fee
+    }
+
+    // ========================================================================
+    // Unstake Cooldown
+    // ========================================================================
+
+    /// Set how long unstake requests wait before RWA can be collected (admin-only).
+    /// While non-zero, instant `unstake`/`withdraw`/`redeem` are disabled.
+    pub fn set_unstake_cooldown(e: &Env, cooldown_seconds: u64) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        e.storage().instance().set(&UNSTAKE_COOLDOWN, &cooldown_seconds);
+    }
+
+    pub fn get_unstake_cooldown(e: &Env) -> u64 {
+        e.storage().instance().get(&UNSTAKE_COOLDOWN).unwrap_or(0)
+    }
+
+    /// Burn `shares` stRWA now and lock the RWA they are worth until the
+    /// cooldown ends. Returns the request id.
+    pub fn request_unstake(e: &Env, user: Address, shares: i128) -> u32 {
+        user.require_auth();
+        Self::accrue_management_fee(e);
+
+        let assets = Self::preview_redeem(e, shares);
+        Self::release_shares(e, &user, shares);
+
+        let id: u32 = e.storage().instance().get(&NEXT_REQUEST_ID).unwrap_or(0);
+        e.storage().instance().set(&NEXT_REQUEST_ID, &(id + 1));
+
+        let unlock_time = e.ledger().timestamp() + Self::get_unstake_cooldown(e);
+        let mut requests = Self::get_unstake_requests(e, user.clone());
+        requests.push_back(UnstakeRequest {
+            id,
+            shares,
+            assets,
+            unlock_time,
+        });
+        Self::save_unstake_requests(e, &user, requests);
+
+        let pending: i128 = e.storage().instance().get(&PENDING_UNSTAKE).unwrap_or(0);
+        e.storage().instance().set(&PENDING_UNSTAKE, &(pending + assets));
+
+        e.events().publish(
+            (symbol_short!("unstk_req"), user),
+            (id, shares, assets, unlock_time)
+        );
+
+        id
+    }
+
+    /// Collect the RWA of a request whose cooldown has ended
+    pub fn complete_unstake(e: &Env, user: Address, request_id: u32) -> i128 {
+        user.require_auth();
+
+        let request = Self::take_unstake_request(e, &user, request_id);
+        if e.ledger().timestamp() < request.unlock_time {
+            panic!("Unstake cooldown not finished");
+        }
+
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        let rwa_token = token::Client::new(e, &rwa_addr);
+        rwa_token.transfer(
+            &e.current_contract_address(),
+            &user,
+            &request.assets
+        );
+
+        e.events().publish(
+            (symbol_short!("unstk_ok"), user),
+            (request_id, request.assets)
+        );
+
+        request.assets
+    }
+
+    /// Cancel a pending request, re-staking its RWA at the current exchange rate.
+    /// Returns the stRWA minted back.
+    pub fn cancel_unstake(e: &Env, user: Address, request_id: u32) -> i128 {
+        user.require_auth();
+        Self::accrue_management_fee(e);
+
+        // Price the shares while the request's RWA is still set aside
+        let assets = Self::get_unstake_requests(e, user.clone())
+            .iter()
+            .find(|r| r.id == request_id)
+            .expect("Unstake request not found")
+            .assets;
+        let shares = Self::convert_to_shares(e, assets);
+
+        Self::take_unstake_request(e, &user, request_id);
+        Self::credit_shares(e, &user, shares);
+
+        e.events().publish(
+            (symbol_short!("unstk_cnl"), user),
+            (request_id, shares)
+        );
+
+        shares
+    }
+
+    /// Pending unstake requests for a user
+    pub fn get_unstake_requests(e: &Env, user: Address) -> Vec<UnstakeRequest> {
+        let requests: Map<Address, Vec<UnstakeRequest>> = e.storage().instance()
+            .get(&UNSTAKE_REQUESTS)
+            .unwrap_or(Map::new(e));
+        requests.get(user).unwrap_or(Vec::new(e))
+    }
+
+    fn save_unstake_requests(e: &Env, user: &Address, user_requests: Vec<UnstakeRequest>) {
+        let mut requests: Map<Address, Vec<UnstakeRequest>> = e.storage().instance()
+            .get(&UNSTAKE_REQUESTS)
+            .unwrap_or(Map::new(e));
+        if user_requests.is_empty() {
+            requests.remove(user.clone());
+        } else {
+            requests.set(user.clone(), user_requests);
+        }
+        e.storage().instance().set(&UNSTAKE_REQUESTS, &requests);
+    }
+
+    /// Remove a user's request and release its RWA from the pending total
+    fn take_unstake_request(e: &Env, user: &Address, request_id: u32) -> UnstakeRequest {
+        let mut requests = Self::get_unstake_requests(e, user.clone());
+        let index = requests
+            .iter()
+            .position(|r| r.id == request_id)
+            .expect("Unstake request not found");
+        let request = requests.get(index as u32).unwrap();
+        requests.remove(index as u32);
+        Self::save_unstake_requests(e, user, requests);
+
+        let pending: i128 = e.storage().instance().get(&PENDING_UNSTAKE).unwrap_or(0);
+        e.storage().instance().set(&PENDING_UNSTAKE, &(pending - request.assets));
+
+        request
+    }
+
+    pub fn admin_fund_yield(e: &Env, amount: i128) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        Self::fund(e, &admin, &Self::usdc(e), amount);
+    }
+
+    /// Fund a lump sum of any registered reward token, claimable immediately (admin-only)
+    pub fn fund_rewards(e: &Env, reward_token: Address, amount: i128) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        Self::fund(e, &admin, &reward_token, amount);
+    }
+
+    /// Fund `amount` of a reward token released linearly to stakers between
+    /// `start` and `end`. Periods may overlap.
+    pub fn fund_yield_period(e: &Env, reward_token: Address, amount: i128, start: u64, end: u64) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if end <= start {
+            panic!("Yield period must end after it starts");
+        }
+        if start < e.ledger().timestamp() {
+            panic!("Yield period cannot start in the past");
+        }
+
+        Self::collect_reward(e, &admin, &reward_token, amount);
+        Self::accrue_streams(e);
+
+        let mut all_streams: Map<Address, Vec<YieldStream>> = e.storage().instance()
+            .get(&YIELD_STREAMS)
+            .unwrap_or(Map::new(e));
+        let mut streams = all_streams.get(reward_token.clone()).unwrap_or(Vec::new(e));
+        streams.push_back(YieldStream {
+            amount,
+            start,
+            end,
+            released: 0,
+        });
+        all_streams.set(reward_token.clone(), streams);
+        e.storage().instance().set(&YIELD_STREAMS, &all_streams);
+
+        e.events().publish(
+            (symbol_short!("yieldstrm"), admin, reward_token),
+            (amount, start, end)
+        );
+    }
+
+    /// Register an additional token stakers are paid in (admin-only)
+    pub fn add_reward_token(e: &Env, reward_token: Address) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        Self::register_reward_token(e, &reward_token);
+    }
+
+    /// Tokens stakers are paid in, USDC first
+    pub fn get_reward_tokens(e: &Env) -> Vec<Address> {
+        e.storage().instance().get(&REWARD_TOKENS).unwrap_or(Vec::new(e))
+    }
+
+    /// Reward token balance held for stakers (funded but not yet claimed)
+    pub fn get_reward_reserve(e: &Env, reward_token: Address) -> i128 {
+        let pools: Map<Address, i128> = e.storage().instance()
+            .get(&TOTAL_YIELD_POOL)
+            .unwrap_or(Map::new(e));
+        pools.get(reward_token).unwrap_or(0)
+    }
+
+    /// Yield periods of a reward token that have not finished releasing
+    pub fn get_yield_streams(e: &Env, reward_token: Address) -> Vec<YieldStream> {
+        let all_streams: Map<Address, Vec<YieldStream>> = e.storage().instance()
+            .get(&YIELD_STREAMS)
+            .unwrap_or(Map::new(e));
+        all_streams.get(reward_token).unwrap_or(Vec::new(e))
+    }
+
+    /// Reward token amount per year currently being released across active yield periods
+    pub fn current_yield_rate(e: &Env, reward_token: Address) -> i128 {
+        let now = e.ledger().timestamp();
+        let mut rate = 0;
+        for stream in Self::get_yield_streams(e, reward_token).iter() {
+            if now >= stream.start && now < stream.end {
+                rate += (stream.amount * SECONDS_PER_YEAR) / (stream.end - stream.start) as i128;
+            }
+        }
+        rate
+    }
+
+    /// Projected APR in basis points at the current yield rate, given the
+    /// price of one whole stRWA in units of the reward token
+    pub fn projected_apr(e: &Env, reward_token: Address, strwa_price: i128) -> i128 {
+        let total_shares = Self::total_shares(e);
+        if total_shares == 0 || strwa_price <= 0 {
+            return 0;
+        }
+
+        // Value of all stRWA in reward token units
+        let total_value = (total_shares * strwa_price) / STRWA_UNIT;
+        if total_value == 0 {
+            return 0;
+        }
+        (Self::current_yield_rate(e, reward_token) * 10000) / total_value
+    }
+
+    pub fn claimable_yield(e: &Env, user: Address) -> i128 {
+        Self::claimable_reward(e, user, Self::usdc(e))
+    }
+
+    /// Unclaimed rewards of one token for a user
+    pub fn claimable_reward(e: &Env, user: Address, reward_token: Address) -> i128 {
+        Self::settled_checkpoint(e, &user)
+            .accrued
+            .get(reward_token)
+            .unwrap_or(0)
+    }
+
+    pub fn claim_yield(e: &Env, user: Address) -> i128 {
+        user.require_auth();
+
+        let claimable = Self::claim(e, &user, &Self::usdc(e));
+        
+        if claimable == 0 {
+            panic!("No yield to claim");
+        }
+        
+        claimable
+    }
+
+    /// Claim one reward token; returns the amount paid
+    pub fn claim_reward(e: &Env, user: Address, reward_token: Address) -> i128 {
+        user.require_auth();
+
+        let claimable = Self::claim(e, &user, &reward_token);
+
+        if claimable == 0 {
+            panic!("No yield to claim");
+        }
+
+        claimable
+    }
+
+    /// Claim every reward token with a balance; returns (token, amount) paid
+    pub fn claim_all(e: &Env, user: Address) -> Vec<(Address, i128)> {
+        user.require_auth();
+
+        let mut claimed = Vec::new(e);
+        for reward_token in Self::get_reward_tokens(e).iter() {
+            let amount = Self::claim(e, &user, &reward_token);
+            if amount > 0 {
+                claimed.push_back((reward_token, amount));
+            }
+        }
+
+        if claimed.is_empty() {
+            panic!("No yield to claim");
+        }
+
+        claimed
+    }
+
+    // ========================================================================
+    // Auto-Compounding
+    // ========================================================================
+
+    /// Opt in or out of reinvesting USDC yield into the stake
+    pub fn set_auto_compound(e: &Env, user: Address, enabled: bool) {
+        user.require_auth();
+
+        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap();
+
+        let mut stake_info = stakes.get(user.clone())
+            .expect("User has no stake");
+
+        stake_info.auto_compound = enabled;
+
+        stakes.set(user.clone(), stake_info);
+        e.storage().instance().set(&STAKE_INFO, &stakes);
+
+        e.events().publish((symbol_short!("auto_cmp"), user), enabled);
+    }
+
+    /// Set the USDC price of one whole RWA at which compounded yield buys RWA
+    /// from the admin (admin-only). The admin must approve the vault to pull
+    /// the RWA it sells.
+    pub fn set_compound_price(e: &Env, rwa_price: i128) {
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+
+        if rwa_price <= 0 {
+            panic!("Price must be positive");
+        }
+
+        e.storage().instance().set(&COMPOUND_PRICE, &rwa_price);
+    }
+
+    pub fn get_compound_price(e: &Env) -> Option<i128> {
+        e.storage().instance().get(&COMPOUND_PRICE)
+    }
+
+    /// Reinvest the claimable USDC yield of opted-in, non-borrowing stakers.
+    /// Permissionless keeper entrypoint; other users are skipped. Returns the
+    /// total stRWA minted.
+    pub fn compound(e: &Env, users: Vec<Address>) -> i128 {
+        let rwa_price: i128 = e.storage().instance()
+            .get(&COMPOUND_PRICE)
+            .expect("Compound price not set");
+
+        Self::accrue_management_fee(e);
+        Self::accrue_streams(e);
+
+        let admin: Address = e.storage().instance().get(&ADMIN_KEY).unwrap();
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        let rwa_token = token::Client::new(e, &rwa_addr);
+        let rwa_unit = 10i128.pow(rwa_token.decimals());
+        let usdc_addr = Self::usdc(e);
+        let stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap_or(Map::new(e));
+
+        let mut total_minted = 0;
+        for user in users.iter() {
+            let compounding = match stakes.get(user.clone()) {
+                Some(stake_info) => stake_info.auto_compound && !stake_info.is_borrower,
+                None => false,
+            };
+            if !compounding {
+                continue;
+            }
+
+            let mut checkpoint = Self::settled_checkpoint(e, &user);
+            let usdc_amount = checkpoint.accrued.get(usdc_addr.clone()).unwrap_or(0);
+            let rwa_amount = (usdc_amount * rwa_unit) / rwa_price;
+            let shares = Self::convert_to_shares(e, rwa_amount);
+            if shares == 0 {
+                continue;
+            }
+
+            checkpoint.accrued.set(usdc_addr.clone(), 0);
+            Self::save_checkpoint(e, &user, checkpoint);
+
+            // Swap the yield for RWA with the admin, then stake it for the user
+            Self::pay_reward(e, &usdc_addr, &admin, usdc_amount);
+            rwa_token.transfer_from(
+                &e.current_contract_address(),
+                &admin,
+                &e.current_contract_address(),
+                &rwa_amount
+            );
+            Self::credit_shares(e, &user, shares);
+            total_minted += shares;
+
+            e.events().publish(
+                (symbol_short!("compound"), user),
+                (usdc_amount, rwa_amount, shares)
+            );
+        }
+
+        total_minted
+    }
+
+    /// Stake record of a user, if any
+    pub fn get_stake_info(e: &Env, user: Address) -> Option<StakeInfo> {
+        let stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap_or(Map::new(e));
+        stakes.get(user)
+    }
+
+    pub fn mark_as_borrower(
+        e: &Env,
+        user: Address,
+        borrowed_amount: i128,
+        loan_period: u64
+    ) {
+        let lending_pool: Address = e.storage().instance()
+            .get(&LENDING_POOL_KEY)
+            .expect("Lending pool not set");
+        lending_pool.require_auth();
+        
+        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap();
+        
+        // Collateral may have been acquired by transfer rather than staked here
+        let mut stake_info = stakes.get(user.clone()).unwrap_or(StakeInfo {
+            amount: 0,
+            timestamp: 0,
+            is_borrower: false,
+            borrowed_amount: 0,
+            loan_period: 0,
+            auto_compound: false,
+        });
+        
+        stake_info.is_borrower = true;
+        stake_info.borrowed_amount = borrowed_amount;
+        stake_info.loan_period = loan_period;
+        stake_info.timestamp = e.ledger().timestamp();
+        
+        stakes.set(user.clone(), stake_info);
+        e.storage().instance().set(&STAKE_INFO, &stakes);
+    }
+
+    pub fn pull_yield_for_repay(e: &Env, user: Address, amount: i128) -> i128 {
+        let lending_pool: Address = e.storage().instance()
+            .get(&LENDING_POOL_KEY)
+            .expect("Lending pool not set");
+        lending_pool.require_auth();
+
+        Self::accrue_streams(e);
+        
+        let usdc_addr = Self::usdc(e);
+        let mut checkpoint = Self::settled_checkpoint(e, &user);
+        let claimable = checkpoint.accrued.get(usdc_addr.clone()).unwrap_or(0);
+        let amount_to_pull = if amount > claimable { claimable } else { amount };
+        
+        if amount_to_pull == 0 {
+            return 0;
+        }
+
+        checkpoint.accrued.set(usdc_addr.clone(), claimable - amount_to_pull);
+        Self::save_checkpoint(e, &user, checkpoint);
+        
+        Self::pay_reward(e, &usdc_addr, &lending_pool, amount_to_pull);
+        
+        amount_to_pull
+    }
+
+    pub fn update_borrowed_amount(e: &Env, user: Address, new_amount: i128) {
+        let lending_pool: Address = e.storage().instance()
+            .get(&LENDING_POOL_KEY)
+            .expect("Lending pool not set");
+        lending_pool.require_auth();
+        
+        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap();
+        
+        let mut stake_info = stakes.get(user.clone())
+            .expect("User has no stake");
+        
+        stake_info.borrowed_amount = new_amount;
+        
+        if new_amount == 0 {
+            stake_info.is_borrower = false;
+        }
+        
+        stakes.set(user.clone(), stake_info);
+        e.storage().instance().set(&STAKE_INFO, &stakes);
+    }
+
+    pub fn set_lp_liquidity_used(e: &Env, lp: Address, amount_used: i128) {
+        let lending_pool: Address = e.storage().instance()
+            .get(&LENDING_POOL_KEY)
             .expect("Lending pool not set");
         lending_pool.require_auth();
         
@@ -435,4 +1264,418 @@ impl RwaVault {
         lp_used.set(lp, amount_used);
         e.storage().instance().set(&LP_LIQUIDITY_USED, &lp_used);
     }
+
+    /// Settle collateral seized in a liquidation (only callable by the lending pool).
+    /// Burns `amount` stRWA the pool has transferred to the vault (the vault can only
+    /// authorize burns of its own balance), removes it from the borrower's stake
+    /// and clears their borrower state. The backing RWA goes to `recipient`, or is
+    /// earmarked for it when the recipient cannot hold RWA yet. Returns the RWA released.
+    pub fn on_collateral_seized(e: &Env, user: Address, amount: i128, recipient: Address) -> i128 {
+        let lending_pool: Address = e.storage().instance()
+            .get(&LENDING_POOL_KEY)
+            .expect("Lending pool not set");
+        lending_pool.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        Self::accrue_management_fee(e);
+        Self::accrue_streams(e);
+
+        // Price the collateral before its stRWA leaves the supply
+        let assets = Self::preview_redeem(e, amount);
+
+        Self::burn_shares(e, &e.current_contract_address(), amount);
+
+        // Pledged stRWA was still credited to the borrower for yield
+        Self::adjust_reward_balance(e, &user, -amount);
+
+        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap_or(Map::new(e));
+        if let Some(stake_info) = stakes.get(user.clone()) {
+            stakes.set(user.clone(), StakeInfo {
+                amount: (stake_info.amount - amount).max(0),
+                timestamp: stake_info.timestamp,
+                is_borrower: false,
+                borrowed_amount: 0,
+                loan_period: 0,
+                auto_compound: stake_info.auto_compound,
+            });
+            e.storage().instance().set(&STAKE_INFO, &stakes);
+        }
+
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        let transferred = RwaTokenClient::new(e, &rwa_addr).allowed(&recipient);
+        if transferred {
+            token::Client::new(e, &rwa_addr).transfer(
+                &e.current_contract_address(),
+                &recipient,
+                &assets
+            );
+        } else {
+            let mut seized: Map<Address, i128> = e.storage().instance()
+                .get(&SEIZED_RWA)
+                .unwrap_or(Map::new(e));
+            let earmarked = seized.get(recipient.clone()).unwrap_or(0);
+            seized.set(recipient.clone(), earmarked + assets);
+            e.storage().instance().set(&SEIZED_RWA, &seized);
+
+            let total: i128 = e.storage().instance().get(&SEIZED_TOTAL).unwrap_or(0);
+            e.storage().instance().set(&SEIZED_TOTAL, &(total + assets));
+        }
+
+        e.events().publish(
+            (symbol_short!("seized"), user, recipient),
+            (amount, assets, transferred)
+        );
+
+        assets
+    }
+
+    /// Seized RWA earmarked for a recipient
+    pub fn get_seized_rwa(e: &Env, recipient: Address) -> i128 {
+        let seized: Map<Address, i128> = e.storage().instance()
+            .get(&SEIZED_RWA)
+            .unwrap_or(Map::new(e));
+        seized.get(recipient).unwrap_or(0)
+    }
+
+    /// Collect earmarked seized RWA once the recipient is on the RWA allowlist
+    pub fn claim_seized_rwa(e: &Env, recipient: Address) -> i128 {
+        recipient.require_auth();
+
+        let amount = Self::get_seized_rwa(e, recipient.clone());
+        if amount == 0 {
+            panic!("No seized RWA to claim");
+        }
+
+        let mut seized: Map<Address, i128> = e.storage().instance()
+            .get(&SEIZED_RWA)
+            .unwrap();
+        seized.remove(recipient.clone());
+        e.storage().instance().set(&SEIZED_RWA, &seized);
+
+        let total: i128 = e.storage().instance().get(&SEIZED_TOTAL).unwrap_or(0);
+        e.storage().instance().set(&SEIZED_TOTAL, &(total - amount));
+
+        let rwa_addr: Address = e.storage().instance().get(&RWA_TOKEN_KEY).unwrap();
+        token::Client::new(e, &rwa_addr).transfer(
+            &e.current_contract_address(),
+            &recipient,
+            &amount
+        );
+
+        amount
+    }
+
+    /// Keep stakes and yield checkpoints in step with stRWA transfers (only callable
+    /// by stRWA, before balances move). Moves to or from the lending pool leave the
+    /// stake and yield with the borrower, since pledged collateral keeps earning for its owner.
+    pub fn on_strwa_transfer(e: &Env, from: Address, to: Address, amount: i128) {
+        let strwa_addr: Address = e.storage().instance().get(&STRWA_TOKEN_KEY).unwrap();
+        strwa_addr.require_auth();
+
+        let lending_pool: Option<Address> = e.storage().instance().get(&LENDING_POOL_KEY);
+        if let Some(pool) = lending_pool {
+            if from == pool || to == pool {
+                return;
+            }
+        }
+
+        Self::move_stake(e, &from, &to, amount);
+
+        Self::accrue_streams(e);
+
+        // Tokens that never earned yield (e.g. seized collateral) stay uncredited
+        let credited = Self::settled_checkpoint(e, &from).balance.min(amount);
+        if credited <= 0 {
+            return;
+        }
+        Self::adjust_reward_balance(e, &from, -credited);
+        Self::adjust_reward_balance(e, &to, credited);
+    }
+
+    /// Move the staked stRWA behind a transfer so the recipient can redeem it.
+    /// Borrowers with an outstanding loan and LPs with liquidity in use may not
+    /// transfer out of the stake those rules protect.
+    fn move_stake(e: &Env, from: &Address, to: &Address, amount: i128) {
+        let mut stakes: Map<Address, StakeInfo> = e.storage().instance()
+            .get(&STAKE_INFO)
+            .unwrap_or(Map::new(e));
+
+        let from_stake = match stakes.get(from.clone()) {
+            Some(stake_info) => stake_info,
+            None => return,
+        };
+        let moved = from_stake.amount.min(amount);
+        if moved <= 0 {
+            return;
+        }
+
+        if from_stake.is_borrower && from_stake.borrowed_amount > 0 {
+            panic!("Cannot transfer stRWA while a loan is outstanding");
+        }
+
+        let lp_used: Map<Address, i128> = e.storage().instance()
+            .get(&LP_LIQUIDITY_USED)
+            .unwrap_or(Map::new(e));
+        let liquidity_in_use = lp_used.get(from.clone()).unwrap_or(0);
+        if liquidity_in_use > 0 && moved > (from_stake.amount - liquidity_in_use) {
+            panic!("Cannot transfer: liquidity is being used for loans");
+        }
+
+        let to_stake = stakes.get(to.clone()).unwrap_or(StakeInfo {
+            amount: 0,
+            timestamp: 0,
+            is_borrower: false,
+            borrowed_amount: 0,
+            loan_period: 0,
+            auto_compound: false,
+        });
+
+        stakes.set(from.clone(), StakeInfo {
+            amount: from_stake.amount - moved,
+            timestamp: from_stake.timestamp,
+            is_borrower: from_stake.is_borrower,
+            borrowed_amount: from_stake.borrowed_amount,
+            loan_period: from_stake.loan_period,
+            auto_compound: from_stake.auto_compound,
+        });
+        stakes.set(to.clone(), StakeInfo {
+            amount: to_stake.amount + moved,
+            timestamp: e.ledger().timestamp(),
+            is_borrower: to_stake.is_borrower,
+            borrowed_amount: to_stake.borrowed_amount,
+            loan_period: to_stake.loan_period,
+            auto_compound: to_stake.auto_compound,
+        });
+
+        e.storage().instance().set(&STAKE_INFO, &stakes);
+    }
+
+    /// Yield checkpoint for a user, settled up to the current accumulators
+    pub fn get_reward_checkpoint(e: &Env, user: Address) -> RewardCheckpoint {
+        Self::settled_checkpoint(e, &user)
+    }
+
+    /// Cumulative rewards per stRWA for a reward token, scaled by 1e18,
+    /// including streamed rewards released since the last update
+    pub fn get_reward_per_share(e: &Env, reward_token: Address) -> i128 {
+        let accumulators: Map<Address, i128> = e.storage().instance()
+            .get(&REWARD_PER_SHARE)
+            .unwrap_or(Map::new(e));
+        let stored = accumulators.get(reward_token.clone()).unwrap_or(0);
+
+        let (released, _) = Self::release_streams(e, &reward_token);
+        let total_shares = Self::total_shares(e);
+        if released == 0 || total_shares == 0 {
+            return stored;
+        }
+
+        let undistributed: Map<Address, i128> = e.storage().instance()
+            .get(&UNDISTRIBUTED_YIELD)
+            .unwrap_or(Map::new(e));
+        let pending = released - Self::performance_fee(e, released)
+            + undistributed.get(reward_token).unwrap_or(0);
+        stored + (pending * REWARD_PRECISION) / total_shares
+    }
+
+    fn usdc(e: &Env) -> Address {
+        e.storage().instance().get(&USDC_TOKEN_KEY).expect("USDC address not set")
+    }
+
+    fn register_reward_token(e: &Env, reward_token: &Address) {
+        let mut reward_tokens = Self::get_reward_tokens(e);
+        if reward_tokens.contains(reward_token) {
+            panic!("Reward token already registered");
+        }
+        reward_tokens.push_back(reward_token.clone());
+        e.storage().instance().set(&REWARD_TOKENS, &reward_tokens);
+
+        e.events().publish((symbol_short!("rwd_add"),), reward_token.clone());
+    }
+
+    /// Pull reward tokens from the admin and hold them for stakers
+    fn collect_reward(e: &Env, admin: &Address, reward_token: &Address, amount: i128) {
+        if !Self::get_reward_tokens(e).contains(reward_token) {
+            panic!("Reward token not registered");
+        }
+
+        let token_client = token::Client::new(e, reward_token);
+        token_client.transfer_from(
+            &e.current_contract_address(),
+            admin,
+            &e.current_contract_address(),
+            &amount
+        );
+
+        let reserve = Self::get_reward_reserve(e, reward_token.clone());
+        Self::set_reward_reserve(e, reward_token, reserve + amount);
+    }
+
+    fn set_reward_reserve(e: &Env, reward_token: &Address, amount: i128) {
+        let mut pools: Map<Address, i128> = e.storage().instance()
+            .get(&TOTAL_YIELD_POOL)
+            .unwrap_or(Map::new(e));
+        pools.set(reward_token.clone(), amount);
+        e.storage().instance().set(&TOTAL_YIELD_POOL, &pools);
+    }
+
+    /// Lump-sum funding, distributed to current holders immediately
+    fn fund(e: &Env, admin: &Address, reward_token: &Address, amount: i128) {
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        Self::collect_reward(e, admin, reward_token, amount);
+
+        Self::accrue_streams(e);
+        Self::distribute_yield(e, reward_token, amount);
+
+        e.events().publish(
+            (symbol_short!("yieldfund"), admin.clone(), reward_token.clone()),
+            amount
+        );
+    }
+
+    /// Settle and pay out a user's rewards of one token; returns the amount paid
+    fn claim(e: &Env, user: &Address, reward_token: &Address) -> i128 {
+        Self::accrue_streams(e);
+
+        let mut checkpoint = Self::settled_checkpoint(e, user);
+        let claimable = checkpoint.accrued.get(reward_token.clone()).unwrap_or(0);
+        if claimable == 0 {
+            return 0;
+        }
+
+        checkpoint.accrued.set(reward_token.clone(), 0);
+        Self::save_checkpoint(e, user, checkpoint);
+
+        Self::pay_reward(e, reward_token, user, claimable);
+
+        e.events().publish((symbol_short!("claim"), user.clone(), reward_token.clone()), claimable);
+
+        claimable
+    }
+
+    fn pay_reward(e: &Env, reward_token: &Address, to: &Address, amount: i128) {
+        let token_client = token::Client::new(e, reward_token);
+        token_client.transfer(
+            &e.current_contract_address(),
+            to,
+            &amount
+        );
+
+        let reserve = Self::get_reward_reserve(e, reward_token.clone());
+        Self::set_reward_reserve(e, reward_token, reserve - amount);
+    }
+
+    /// Streamed rewards due since the last update, and the streams still running
+    fn release_streams(e: &Env, reward_token: &Address) -> (i128, Vec<YieldStream>) {
+        let now = e.ledger().timestamp();
+        let mut released = 0;
+        let mut remaining = Vec::new(e);
+
+        for mut stream in Self::get_yield_streams(e, reward_token.clone()).iter() {
+            if now > stream.start {
+                let elapsed = now.min(stream.end) - stream.start;
+                let due = (stream.amount * elapsed as i128) / (stream.end - stream.start) as i128;
+                released += due - stream.released;
+                stream.released = due;
+            }
+            if now < stream.end {
+                remaining.push_back(stream);
+            }
+        }
+        (released, remaining)
+    }
+
+    /// Add streamed rewards released so far to each token's accumulator
+    fn accrue_streams(e: &Env) {
+        let mut all_streams: Map<Address, Vec<YieldStream>> = e.storage().instance()
+            .get(&YIELD_STREAMS)
+            .unwrap_or(Map::new(e));
+
+        for reward_token in all_streams.keys().iter() {
+            let (released, remaining) = Self::release_streams(e, &reward_token);
+            all_streams.set(reward_token.clone(), remaining);
+            if released > 0 {
+                Self::distribute_yield(e, &reward_token, released);
+            }
+        }
+        e.storage().instance().set(&YIELD_STREAMS, &all_streams);
+    }
+
+    fn distribute_yield(e: &Env, reward_token: &Address, amount: i128) {
+        let amount = Self::take_performance_fee(e, reward_token, amount);
+
+        let mut undistributed: Map<Address, i128> = e.storage().instance()
+            .get(&UNDISTRIBUTED_YIELD)
+            .unwrap_or(Map::new(e));
+        let pending = amount + undistributed.get(reward_token.clone()).unwrap_or(0);
+        let total_shares = Self::total_shares(e);
+
+        // Nobody to pay yet: hold the rewards for the first stakers
+        if total_shares == 0 {
+            undistributed.set(reward_token.clone(), pending);
+            e.storage().instance().set(&UNDISTRIBUTED_YIELD, &undistributed);
+            return;
+        }
+
+        let mut accumulators: Map<Address, i128> = e.storage().instance()
+            .get(&REWARD_PER_SHARE)
+            .unwrap_or(Map::new(e));
+        let stored = accumulators.get(reward_token.clone()).unwrap_or(0);
+        accumulators.set(reward_token.clone(), stored + (pending * REWARD_PRECISION) / total_shares);
+        e.storage().instance().set(&REWARD_PER_SHARE, &accumulators);
+
+        undistributed.remove(reward_token.clone());
+        e.storage().instance().set(&UNDISTRIBUTED_YIELD, &undistributed);
+    }
+
+    fn settled_checkpoint(e: &Env, user: &Address) -> RewardCheckpoint {
+        let rewards: Map<Address, RewardCheckpoint> = e.storage().instance()
+            .get(&REWARDS)
+            .unwrap_or(Map::new(e));
+        let mut checkpoint = rewards.get(user.clone()).unwrap_or(RewardCheckpoint {
+            balance: 0,
+            reward_per_share_paid: Map::new(e),
+            accrued: Map::new(e),
+        });
+
+        for reward_token in Self::get_reward_tokens(e).iter() {
+            let reward_per_share = Self::get_reward_per_share(e, reward_token.clone());
+            let paid = checkpoint.reward_per_share_paid.get(reward_token.clone()).unwrap_or(0);
+            let accrued = checkpoint.accrued.get(reward_token.clone()).unwrap_or(0);
+
+            checkpoint.accrued.set(
+                reward_token.clone(),
+                accrued + (checkpoint.balance * (reward_per_share - paid)) / REWARD_PRECISION,
+            );
+            checkpoint.reward_per_share_paid.set(reward_token, reward_per_share);
+        }
+        checkpoint
+    }
+
+    fn save_checkpoint(e: &Env, user: &Address, checkpoint: RewardCheckpoint) {
+        let mut rewards: Map<Address, RewardCheckpoint> = e.storage().instance()
+            .get(&REWARDS)
+            .unwrap_or(Map::new(e));
+        rewards.set(user.clone(), checkpoint);
+        e.storage().instance().set(&REWARDS, &rewards);
+    }
+
+    /// Settle a user's rewards, then change the stRWA balance they accrue on
+    fn adjust_reward_balance(e: &Env, user: &Address, delta: i128) {
+        let mut checkpoint = Self::settled_checkpoint(e, user);
+        checkpoint.balance = (checkpoint.balance + delta).max(0);
+        Self::save_checkpoint(e, user, checkpoint);
+    }
+
+    /// Get the RWA token this vault holds
+    pub fn get_rwa_token(e: &Env) -> Address {
+        e.storage().instance().get(&RWA_TOKEN_KEY).unwrap()
+    }
 }
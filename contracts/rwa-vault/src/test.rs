@@ -1,7 +1,11 @@
 #![cfg(test)]
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
 
 use crate::contract::{RwaVault, RwaVaultClient};
 use mock_rwa_token::contract::{MockRwaToken, MockRwaTokenClient};
@@ -127,4 +131,635 @@ fn test_lp_cannot_unstake_when_liquidity_used() {
     setup.vault_client.set_lp_liquidity_used(&setup.user, &600);
 
     setup.vault_client.unstake(&setup.user, &500);
-}
\ No newline at end of file
+}
+#[test]
+fn test_stake_enrolls_user_when_vault_is_manager() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+
+    // User drops off the allowlist; the vault holds the manager role
+    setup.rwa_token_client.disallow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.grant_role(
+        &setup.admin,
+        &setup.vault_client.address,
+        &symbol_short!("manager"),
+    );
+
+    setup.vault_client.stake(&setup.user, &400);
+
+    assert!(setup.rwa_token_client.allowed(&setup.user));
+    assert_eq!(setup.rwa_token_client.balance(&setup.vault_client.address), 400);
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 400);
+}
+
+#[test]
+#[should_panic(expected = "User is not on the RWA allowlist")]
+fn test_stake_requires_allowlist_without_manager_role() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.rwa_token_client.disallow_user(&setup.user, &setup.admin);
+
+    setup.vault_client.stake(&setup.user, &400);
+}
+
+#[test]
+#[should_panic]
+fn test_stake_requires_rwa_balance() {
+    let setup = setup_test();
+
+    // Nothing to deposit, so no stRWA can be minted
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.vault_client.stake(&setup.user, &400);
+}
+
+#[test]
+fn test_share_price_tracks_vault_holdings() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+
+    let shares = setup.vault_client.deposit(&setup.user, &1000, &setup.user);
+    assert_eq!(shares, 1000);
+    assert_eq!(setup.vault_client.total_assets(), 1000);
+
+    // RWA accrues to the vault (e.g. reinvested coupons): each share is worth more.
+    // Conversions round down in the vault's favour.
+    setup.rwa_token_client.transfer(&setup.admin, &setup.vault_client.address, &500);
+    assert_eq!(setup.vault_client.convert_to_assets(&1000), 1499);
+    assert_eq!(setup.vault_client.preview_deposit(&1500), 1000);
+
+    let assets = setup.vault_client.redeem(&setup.user, &400, &setup.user);
+    assert_eq!(assets, 599);
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 600);
+    assert_eq!(setup.rwa_token_client.balance(&setup.user), 599);
+}
+
+#[test]
+fn test_mint_and_withdraw_round_against_user() {
+    let setup = setup_test();
+    let other = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.allow_user(&other, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &3000);
+
+    setup.vault_client.deposit(&setup.user, &1000, &setup.user);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.vault_client.address, &500);
+
+    // 1000 shares back 1500 RWA: minting 3 shares costs 5 RWA (4.5 rounded up)
+    assert_eq!(setup.vault_client.preview_mint(&3), 5);
+    let assets = setup.vault_client.mint(&setup.user, &3, &other);
+    assert_eq!(assets, 5);
+    assert_eq!(setup.strwa_token_client.balance(&other), 3);
+
+    // Withdrawing 5 RWA burns 4 shares (3.33 rounded up)
+    let shares = setup.vault_client.withdraw(&setup.user, &5, &setup.user);
+    assert_eq!(shares, 4);
+}
+
+fn fund_yield(setup: &TestSetup, amount: i128) {
+    setup
+        .usdc_token_client
+        .approve(&setup.admin, &setup.vault_client.address, &amount, &1000);
+    setup.vault_client.admin_fund_yield(&amount);
+}
+
+#[test]
+fn test_late_staker_does_not_capture_earlier_yield() {
+    let setup = setup_test();
+    let late = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.allow_user(&late, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.rwa_token_client.transfer(&setup.admin, &late, &1000);
+
+    setup.vault_client.stake(&setup.user, &1000);
+    fund_yield(&setup, 10_000);
+
+    setup.vault_client.stake(&late, &1000);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 10_000);
+    assert_eq!(setup.vault_client.claimable_yield(&late), 0);
+
+    fund_yield(&setup, 4_000);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 12_000);
+    assert_eq!(setup.vault_client.claimable_yield(&late), 2_000);
+}
+
+#[test]
+fn test_claim_then_transfer_cannot_double_claim() {
+    let setup = setup_test();
+    let second_wallet = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    fund_yield(&setup, 10_000);
+
+    assert_eq!(setup.vault_client.claim_yield(&setup.user), 10_000);
+
+    setup
+        .strwa_token_client
+        .transfer(&setup.user, &second_wallet, &1000);
+    assert_eq!(setup.vault_client.claimable_yield(&second_wallet), 0);
+
+    // Future yield follows the tokens
+    fund_yield(&setup, 5_000);
+    assert_eq!(setup.vault_client.claimable_yield(&second_wallet), 5_000);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 0);
+}
+
+#[test]
+fn test_transferred_strwa_can_be_redeemed() {
+    let setup = setup_test();
+    let second_wallet = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.allow_user(&second_wallet, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    setup
+        .strwa_token_client
+        .transfer(&setup.user, &second_wallet, &400);
+
+    // The stake follows the tokens
+    assert_eq!(setup.vault_client.get_stake_info(&setup.user).unwrap().amount, 600);
+    assert_eq!(setup.vault_client.get_stake_info(&second_wallet).unwrap().amount, 400);
+
+    setup.vault_client.redeem(&second_wallet, &400, &second_wallet);
+    assert_eq!(setup.rwa_token_client.balance(&second_wallet), 400);
+    assert_eq!(setup.strwa_token_client.balance(&second_wallet), 0);
+}
+
+#[test]
+#[should_panic(expected = "Cannot transfer stRWA while a loan is outstanding")]
+fn test_borrower_cannot_transfer_stake() {
+    let setup = setup_test();
+    let second_wallet = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.mark_as_borrower(&setup.user, &500, &1000);
+
+    setup
+        .strwa_token_client
+        .transfer(&setup.user, &second_wallet, &400);
+}
+
+fn fund_yield_period(setup: &TestSetup, amount: i128, start: u64, end: u64) {
+    setup
+        .usdc_token_client
+        .approve(&setup.admin, &setup.vault_client.address, &amount, &1000);
+    setup.vault_client.fund_yield_period(
+        &setup.usdc_token_client.address,
+        &amount,
+        &start,
+        &end,
+    );
+}
+
+#[test]
+fn test_yield_period_streams_linearly() {
+    let setup = setup_test();
+    let late = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.allow_user(&late, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.rwa_token_client.transfer(&setup.admin, &late, &1000);
+
+    setup.vault_client.stake(&setup.user, &1000);
+    fund_yield_period(&setup, 1_000, 100, 1_100);
+
+    // Nothing is claimable up front
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 0);
+
+    setup.env.ledger().set_timestamp(600);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 500);
+
+    // A staker joining halfway only shares the second half
+    setup.vault_client.stake(&late, &1000);
+
+    setup.env.ledger().set_timestamp(2_000);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 750);
+    assert_eq!(setup.vault_client.claimable_yield(&late), 250);
+
+    // Finished periods are dropped once yield is settled
+    setup.vault_client.claim_yield(&late);
+    assert_eq!(
+        setup
+            .vault_client
+            .get_yield_streams(&setup.usdc_token_client.address)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_overlapping_yield_periods_rate_and_apr() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    fund_yield_period(&setup, 1_000, 100, 1_100);
+    fund_yield_period(&setup, 2_000, 600, 2_600);
+
+    let usdc = setup.usdc_token_client.address.clone();
+
+    setup.env.ledger().set_timestamp(300);
+    assert_eq!(setup.vault_client.current_yield_rate(&usdc), 31_536_000);
+
+    setup.env.ledger().set_timestamp(700);
+    assert_eq!(setup.vault_client.current_yield_rate(&usdc), 63_072_000);
+
+    // 1000 units of stRWA priced at 10 USDC units each
+    let price = 10 * 1_000_000_000_000_000_000i128;
+    assert_eq!(setup.vault_client.projected_apr(&usdc, &price), 63_072_000);
+}
+
+#[test]
+#[should_panic(expected = "Yield period must end after it starts")]
+fn test_yield_period_requires_valid_window() {
+    let setup = setup_test();
+    fund_yield_period(&setup, 1_000, 500, 500);
+}
+
+#[test]
+fn test_multiple_reward_tokens_claim_all() {
+    let setup = setup_test();
+    let rwa = setup.rwa_token_client.address.clone();
+    let usdc = setup.usdc_token_client.address.clone();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    // T-bill style vault also pays rewards in the RWA token itself
+    setup.vault_client.add_reward_token(&rwa);
+    assert_eq!(
+        setup.vault_client.get_reward_tokens(),
+        vec![&setup.env, usdc.clone(), rwa.clone()]
+    );
+
+    fund_yield(&setup, 10_000);
+    setup
+        .rwa_token_client
+        .approve(&setup.admin, &setup.vault_client.address, &300, &1000);
+    setup.vault_client.fund_rewards(&rwa, &300);
+
+    // RWA held for rewards does not inflate the share price
+    assert_eq!(setup.vault_client.total_assets(), 1000);
+    assert_eq!(setup.vault_client.claimable_reward(&setup.user, &rwa), 300);
+
+    let claimed = setup.vault_client.claim_all(&setup.user);
+    assert_eq!(claimed.len(), 2);
+    assert_eq!(setup.usdc_token_client.balance(&setup.user), 10_000);
+    assert_eq!(setup.rwa_token_client.balance(&setup.user), 300);
+    assert_eq!(setup.vault_client.get_reward_reserve(&rwa), 0);
+}
+
+#[test]
+#[should_panic(expected = "Reward token not registered")]
+fn test_fund_unregistered_reward_token() {
+    let setup = setup_test();
+
+    setup
+        .rwa_token_client
+        .approve(&setup.admin, &setup.vault_client.address, &300, &1000);
+    setup
+        .vault_client
+        .fund_rewards(&setup.rwa_token_client.address, &300);
+}
+
+#[test]
+fn test_unstake_request_cooldown_flow() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.set_unstake_cooldown(&(7 * 24 * 60 * 60));
+
+    setup.env.ledger().set_timestamp(1_000);
+    let id = setup.vault_client.request_unstake(&setup.user, &400);
+
+    // stRWA is burned and the RWA is set aside
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 600);
+    assert_eq!(setup.vault_client.total_assets(), 600);
+    let requests = setup.vault_client.get_unstake_requests(&setup.user);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests.get(0).unwrap().assets, 400);
+
+    setup.env.ledger().set_timestamp(1_000 + 7 * 24 * 60 * 60);
+    assert_eq!(setup.vault_client.complete_unstake(&setup.user, &id), 400);
+    assert_eq!(setup.rwa_token_client.balance(&setup.user), 400);
+    assert_eq!(setup.vault_client.get_unstake_requests(&setup.user).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unstake cooldown not finished")]
+fn test_complete_unstake_before_cooldown() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.set_unstake_cooldown(&3600);
+
+    let id = setup.vault_client.request_unstake(&setup.user, &400);
+    setup.vault_client.complete_unstake(&setup.user, &id);
+}
+
+#[test]
+fn test_cancel_unstake_restores_stake() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.set_unstake_cooldown(&3600);
+
+    let id = setup.vault_client.request_unstake(&setup.user, &400);
+    assert_eq!(setup.vault_client.cancel_unstake(&setup.user, &id), 400);
+
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 1000);
+    assert_eq!(setup.vault_client.total_assets(), 1000);
+    assert_eq!(setup.vault_client.get_unstake_requests(&setup.user).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unstake cooldown active: use request_unstake")]
+fn test_instant_unstake_disabled_during_cooldown() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.set_unstake_cooldown(&3600);
+
+    setup.vault_client.unstake(&setup.user, &400);
+}
+
+#[test]
+fn test_foreclosure_fee_routed_to_treasury() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    setup.vault_client.set_exit_config(&1000, &300, &Some(treasury.clone()));
+    setup.vault_client.mark_as_borrower(&setup.user, &500, &1000);
+
+    // Past the 10% lockup
+    setup.env.ledger().set_timestamp(100);
+    setup.vault_client.unstake(&setup.user, &500);
+
+    assert_eq!(setup.rwa_token_client.balance(&setup.user), 500);
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 485);
+    assert_eq!(setup.strwa_token_client.balance(&treasury), 15);
+    assert_eq!(setup.strwa_token_client.total_supply(), 500);
+}
+
+#[test]
+fn test_no_foreclosure_fee_without_treasury() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.mark_as_borrower(&setup.user, &500, &1000);
+
+    setup.env.ledger().set_timestamp(200);
+    setup.vault_client.unstake(&setup.user, &100);
+
+    // Until a treasury is configured no fee is charged
+    assert_eq!(setup.vault_client.get_exit_config().foreclosure_fee_bps, 0);
+    assert_eq!(setup.strwa_token_client.balance(&setup.lending_pool), 0);
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 900);
+    assert_eq!(setup.strwa_token_client.total_supply(), 900);
+}
+
+#[test]
+#[should_panic(expected = "Treasury required")]
+fn test_foreclosure_fee_requires_treasury() {
+    let setup = setup_test();
+
+    setup.vault_client.set_exit_config(&1000, &300, &None);
+}
+
+#[test]
+#[should_panic(expected = "Cannot unstake during lockup period")]
+fn test_configured_lockup_blocks_unstake() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    setup.vault_client.set_exit_config(&5000, &500, &Some(treasury));
+    setup.vault_client.mark_as_borrower(&setup.user, &500, &1000);
+
+    setup.env.ledger().set_timestamp(400);
+    setup.vault_client.unstake(&setup.user, &100);
+}
+
+#[test]
+fn test_management_fee_minted_to_recipient() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1_000_000);
+
+    setup.vault_client.set_fee_config(&200, &0, &Some(treasury.clone()));
+    setup.vault_client.stake(&setup.user, &1_000_000);
+
+    // One year at 2% of total_assets
+    setup.env.ledger().set_timestamp(365 * 24 * 60 * 60);
+    let owed = setup.vault_client.accrued_management_fee();
+    assert_eq!(owed, 20_408);
+
+    assert_eq!(setup.vault_client.accrue_management_fee(), owed);
+    assert_eq!(setup.strwa_token_client.balance(&treasury), owed);
+    assert_eq!(setup.vault_client.convert_to_assets(&owed), 19_999);
+    assert_eq!(setup.vault_client.accrued_management_fee(), 0);
+}
+
+#[test]
+fn test_management_fee_below_one_share_carries_forward() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1_000_000);
+
+    setup.vault_client.set_fee_config(&200, &0, &Some(treasury.clone()));
+    setup.vault_client.stake(&setup.user, &1_000_000);
+
+    // Too little time for a whole share of fee
+    setup.env.ledger().set_timestamp(1_000);
+    assert_eq!(setup.vault_client.accrue_management_fee(), 0);
+
+    // The earlier period still counts towards the next mint
+    setup.env.ledger().set_timestamp(2_000);
+    assert_eq!(setup.vault_client.accrue_management_fee(), 1);
+    assert_eq!(setup.strwa_token_client.balance(&treasury), 1);
+}
+
+#[test]
+fn test_performance_fee_taken_from_yield() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    setup.vault_client.set_fee_config(&0, &1000, &Some(treasury.clone()));
+    fund_yield(&setup, 1000);
+
+    let usdc = setup.usdc_token_client.address.clone();
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 900);
+    assert_eq!(setup.vault_client.get_accrued_fees(&usdc), 100);
+
+    assert_eq!(setup.vault_client.claim_fees(&usdc), 100);
+    assert_eq!(setup.usdc_token_client.balance(&treasury), 100);
+    assert_eq!(setup.vault_client.get_accrued_fees(&usdc), 0);
+    assert_eq!(setup.vault_client.claim_yield(&setup.user), 900);
+}
+
+#[test]
+#[should_panic(expected = "Management fee too high")]
+fn test_management_fee_cap() {
+    let setup = setup_test();
+    let treasury = Address::generate(&setup.env);
+
+    setup.vault_client.set_fee_config(&501, &0, &Some(treasury));
+}
+
+#[test]
+fn test_compound_reinvests_yield_for_opted_in_stakers() {
+    let setup = setup_test();
+    let other = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.allow_user(&other, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.rwa_token_client.transfer(&setup.admin, &other, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup.vault_client.stake(&other, &1000);
+
+    setup.vault_client.set_auto_compound(&setup.user, &true);
+    assert!(setup.vault_client.get_stake_info(&setup.user).unwrap().auto_compound);
+
+    // 0.5 USDC units buy 1 RWA unit; the admin sells from its balance
+    setup.vault_client.set_compound_price(&500_000_000_000_000_000);
+    setup
+        .rwa_token_client
+        .approve(&setup.admin, &setup.vault_client.address, &1000, &1000);
+
+    fund_yield(&setup, 1000);
+    let admin_usdc = setup.usdc_token_client.balance(&setup.admin);
+
+    let minted = setup.vault_client.compound(&vec![&setup.env, setup.user.clone(), other.clone()]);
+
+    assert_eq!(minted, 1000);
+    assert_eq!(setup.strwa_token_client.balance(&setup.user), 2000);
+    assert_eq!(setup.vault_client.claimable_yield(&setup.user), 0);
+    assert_eq!(setup.vault_client.total_assets(), 3000);
+    assert_eq!(setup.usdc_token_client.balance(&setup.admin), admin_usdc + 500);
+
+    // Stakers who did not opt in keep their USDC
+    assert_eq!(setup.strwa_token_client.balance(&other), 1000);
+    assert_eq!(setup.vault_client.claimable_yield(&other), 500);
+}
+
+#[test]
+#[should_panic(expected = "Compound price not set")]
+fn test_compound_requires_price() {
+    let setup = setup_test();
+
+    setup.vault_client.compound(&vec![&setup.env, setup.user.clone()]);
+}
+
+#[test]
+fn test_collateral_seizure_releases_backing_rwa() {
+    let setup = setup_test();
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+
+    // Pledge 600 stRWA to the pool and borrow against it
+    setup
+        .strwa_token_client
+        .transfer(&setup.user, &setup.lending_pool, &600);
+    setup.vault_client.mark_as_borrower(&setup.user, &500, &1000);
+
+    // The pool hands the seized stRWA to the vault to burn
+    setup
+        .strwa_token_client
+        .transfer(&setup.lending_pool, &setup.vault_client.address, &600);
+
+    let admin_rwa = setup.rwa_token_client.balance(&setup.admin);
+    let released = setup
+        .vault_client
+        .on_collateral_seized(&setup.user, &600, &setup.admin);
+
+    assert_eq!(released, 600);
+    assert_eq!(setup.rwa_token_client.balance(&setup.admin), admin_rwa + 600);
+    assert_eq!(setup.strwa_token_client.total_supply(), 400);
+    assert_eq!(setup.vault_client.total_assets(), 400);
+
+    let stake = setup.vault_client.get_stake_info(&setup.user).unwrap();
+    assert_eq!(stake.amount, 400);
+    assert!(!stake.is_borrower);
+    assert_eq!(stake.borrowed_amount, 0);
+    assert_eq!(setup.vault_client.get_reward_checkpoint(&setup.user).balance, 400);
+
+    // The remaining stake is still fully redeemable
+    setup.vault_client.unstake(&setup.user, &400);
+    assert_eq!(setup.rwa_token_client.balance(&setup.user), 400);
+}
+
+#[test]
+fn test_collateral_seizure_earmarks_for_unlisted_recipient() {
+    let setup = setup_test();
+    let recipient = Address::generate(&setup.env);
+
+    setup.rwa_token_client.allow_user(&setup.user, &setup.admin);
+    setup.rwa_token_client.transfer(&setup.admin, &setup.user, &1000);
+    setup.vault_client.stake(&setup.user, &1000);
+    setup
+        .strwa_token_client
+        .transfer(&setup.user, &setup.lending_pool, &600);
+    setup
+        .strwa_token_client
+        .transfer(&setup.lending_pool, &setup.vault_client.address, &600);
+
+    setup
+        .vault_client
+        .on_collateral_seized(&setup.user, &600, &recipient);
+
+    assert_eq!(setup.vault_client.get_seized_rwa(&recipient), 600);
+    assert_eq!(setup.vault_client.total_assets(), 400);
+    assert_eq!(setup.vault_client.preview_redeem(&400), 400);
+
+    setup.rwa_token_client.allow_user(&recipient, &setup.admin);
+    assert_eq!(setup.vault_client.claim_seized_rwa(&recipient), 600);
+    assert_eq!(setup.rwa_token_client.balance(&recipient), 600);
+    assert_eq!(setup.vault_client.get_seized_rwa(&recipient), 0);
+    assert_eq!(setup.vault_client.total_assets(), 400);
+}
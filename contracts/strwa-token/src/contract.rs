@@ -1,4 +1,6 @@
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, Env, IntoVal, MuxedAddress, String, Symbol,
+};
 use stellar_tokens::fungible::{Base, FungibleToken};
 use stellar_macros::default_impl;
 
@@ -58,6 +60,18 @@ impl StRwaToken {
         // Burn tokens
         Base::burn(e, &from, amount);
     }
+
+    /// Let the vault checkpoint yield before stRWA changes hands
+    fn notify_vault(e: &Env, from: &Address, to: &Address, amount: i128) {
+        let vault: Option<Address> = e.storage().instance().get(&symbol_short!("vault"));
+        if let Some(vault) = vault {
+            e.invoke_contract::<()>(
+                &vault,
+                &Symbol::new(e, "on_strwa_transfer"),
+                (from.clone(), to.clone(), amount).into_val(e),
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,4 +87,14 @@ impl StRwaToken {
 #[contractimpl]
 impl FungibleToken for StRwaToken {
     type ContractType = Base;  // No restrictions on transfers
+
+    fn transfer(e: &Env, from: Address, to: MuxedAddress, amount: i128) {
+        Self::notify_vault(e, &from, &to.address(), amount);
+        Base::transfer(e, &from, &to, amount);
+    }
+
+    fn transfer_from(e: &Env, spender: Address, from: Address, to: Address, amount: i128) {
+        Self::notify_vault(e, &from, &to, amount);
+        Base::transfer_from(e, &spender, &from, &to, amount);
+    }
 }
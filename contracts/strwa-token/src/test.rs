@@ -1,10 +1,19 @@
 #![cfg(test)]
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, String};
 
 use crate::contract::{StRwaToken, StRwaTokenClient};
 
+// Vault stand-in that accepts the transfer hook
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn on_strwa_transfer(_e: Env, _from: Address, _to: Address, _amount: i128) {}
+}
+
 fn create_strwa_token_contract<'a>(e: &Env, admin: &Address) -> StRwaTokenClient<'a> {
     let address = e.register(StRwaToken, ());
     let client = StRwaTokenClient::new(e, &address);
@@ -94,7 +103,7 @@ fn test_unrestricted_transfer() {
     e.mock_all_auths();
 
     let admin = Address::generate(&e);
-    let vault = Address::generate(&e);
+    let vault = e.register(MockVault, ());
     let user1 = Address::generate(&e);
     let user2 = Address::generate(&e);
     let client = create_strwa_token_contract(&e, &admin);
@@ -114,7 +123,7 @@ fn test_can_use_in_defi() {
     e.mock_all_auths();
 
     let admin = Address::generate(&e);
-    let vault = Address::generate(&e);
+    let vault = e.register(MockVault, ());
     let user = Address::generate(&e);
     let lending_pool = Address::generate(&e);
     let client = create_strwa_token_contract(&e, &admin);
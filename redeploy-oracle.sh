@@ -26,6 +26,7 @@ NEW_ORACLE=$(stellar contract deploy \
   --source $SOURCE_ACCOUNT \
   --network $NETWORK \
   -- \
+  --admin $DEPLOYER \
   --bot_address $BOT_ADDRESS \
   2>&1 | grep -E '^C[A-Z0-9]{55}$')
 